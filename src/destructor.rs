@@ -1,4 +1,4 @@
-use std::mem::MaybeUninit;
+use std::{mem::MaybeUninit, ops::Deref};
 
 use vkobjects::DeviceManuallyDestroyed;
 
@@ -16,8 +16,19 @@ impl<const N: usize> Destructor<N> {
   }
 
   pub fn push(&mut self, ptr: *const dyn DeviceManuallyDestroyed) {
-    self.len += 1;
+    assert!(self.len < N, "Destructor capacity exceeded");
     self.objs[self.len] = MaybeUninit::new(ptr);
+    self.len += 1;
+  }
+
+  // stop tracking an object that was already destroyed or whose ownership was moved elsewhere
+  // keeps the destruction order of the remaining objects
+  pub fn remove(&mut self, ptr: *const dyn DeviceManuallyDestroyed) {
+    let i = (0..self.len)
+      .find(|&i| unsafe { std::ptr::addr_eq(self.objs[i].assume_init(), ptr) })
+      .expect("Removing object not present in Destructor");
+    self.objs.copy_within(i + 1..self.len, i);
+    self.len -= 1;
   }
 
   pub unsafe fn fire(&self, device: &ash::Device) {
@@ -30,3 +41,82 @@ impl<const N: usize> Destructor<N> {
     }
   }
 }
+
+// Destroys the held object when dropped, unless ownership is taken back with into_inner
+// Makes intermediate objects get cleaned up on any early return or panic
+pub struct Owned<'a, T: DeviceManuallyDestroyed> {
+  obj: Option<T>,
+  device: &'a ash::Device,
+}
+
+impl<'a, T: DeviceManuallyDestroyed> Owned<'a, T> {
+  pub fn new(device: &'a ash::Device, obj: T) -> Self {
+    Self {
+      obj: Some(obj),
+      device,
+    }
+  }
+
+  pub fn into_inner(mut self) -> T {
+    self.obj.take().unwrap()
+  }
+}
+
+impl<'a, T: DeviceManuallyDestroyed> Deref for Owned<'a, T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    self.obj.as_ref().unwrap()
+  }
+}
+
+impl<'a, T: DeviceManuallyDestroyed> Drop for Owned<'a, T> {
+  fn drop(&mut self) {
+    if let Some(obj) = self.obj.take() {
+      unsafe {
+        obj.destroy_self(self.device);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::Cell, ptr};
+
+  use ash::vk;
+  use vkobjects::DeviceManuallyDestroyed;
+
+  use super::Owned;
+
+  // counts destroy_self calls instead of destroying anything
+  struct CountedDestroy<'c>(&'c Cell<usize>);
+
+  impl DeviceManuallyDestroyed for CountedDestroy<'_> {
+    unsafe fn destroy_self(&self, _device: &ash::Device) {
+      self.0.set(self.0.get() + 1);
+    }
+  }
+
+  // never called into by the mock
+  fn null_device() -> ash::Device {
+    unsafe { ash::Device::load_with(|_| ptr::null(), vk::Device::null()) }
+  }
+
+  #[test]
+  fn drop_destroys_once() {
+    let device = null_device();
+    let count = Cell::new(0);
+    drop(Owned::new(&device, CountedDestroy(&count)));
+    assert_eq!(count.get(), 1);
+  }
+
+  #[test]
+  fn into_inner_does_not_destroy() {
+    let device = null_device();
+    let count = Cell::new(0);
+    let obj = Owned::new(&device, CountedDestroy(&count)).into_inner();
+    drop(obj);
+    assert_eq!(count.get(), 0);
+  }
+}
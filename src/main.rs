@@ -4,7 +4,9 @@ mod render;
 mod threads_manager;
 
 use ash::vk;
-use render::{FrameRenderError, InitializationError, PreWindowInit, PreWindowInitError};
+use render::{
+  FrameRenderError, InitializationError, PreWindowInit, PreWindowInitError, SelectionLogging,
+};
 use std::{
   ffi::CStr,
   time::{Duration, Instant},
@@ -40,6 +42,12 @@ const SCREENSHOT_SAVE_FILE: &str = "last_screenshot.png";
 const BACKGROUND_COLOR: vk::ClearColorValue = vk::ClearColorValue {
   float32: [0.5, 0.5, 0.5, 1.0],
 };
+// background colors (normalized rgba) cycled through with F4, starting after BACKGROUND_COLOR
+const BACKGROUND_COLOR_CYCLE: [[f32; 4]; 3] = [
+  [0.1, 0.1, 0.1, 1.0],
+  [0.2, 0.3, 0.5, 1.0],
+  [0.5, 0.5, 0.5, 1.0],
+];
 // color exterior the game area
 // (that appears if window is resized to a size with ratio different that in RESOLUTION)
 const OUT_OF_BOUNDS_AREA_COLOR: vk::ClearColorValue = vk::ClearColorValue {
@@ -69,6 +77,20 @@ const RENDER_UNTIL_FRAME: usize = usize::MAX;
 
 const DEBUG_PRINT_FRAME_INFO: bool = false;
 
+// Silent, Summary (only the selected device) or Verbose (every candidate and why it was skipped)
+const DEVICE_SELECTION_LOGGING: SelectionLogging = SelectionLogging::Summary;
+// rank devices with a dedicated transfer family (used to copy new particles) above those where
+// transfers fall back to the compute or graphics family
+// device type still decides between devices that are equal in this regard
+const PREFER_DEDICATED_TRANSFER: bool = true;
+// rank devices only by their total device local memory instead of by queue families and device
+// type, for example to pick the GPU with the most VRAM between multiple discrete ones
+const RANK_DEVICES_BY_LOCAL_MEMORY: bool = false;
+// create the new particles buffer with concurrent sharing between the transfer and compute
+// families instead of transferring its ownership after each copy
+// only has an effect if the two families are different
+const SHARE_NEW_PARTICLES_CONCURRENTLY: bool = false;
+
 // This application doesn't use dynamic pipeline size, so resizing is expensive
 // If a small resize happens (for example while resizing with the mouse) this usually means that
 // more are to come, and recreating objects each frame can make the application lag
@@ -96,6 +118,7 @@ struct StartedStatus {
   pub occluded: bool,
   pub suspended: bool,
   pub waiting_for_window_events: bool,
+  pub background_color_i: usize,
 }
 
 struct App {
@@ -166,6 +189,7 @@ impl RenderStatus {
           occluded: false,
           suspended: false,
           waiting_for_window_events: false,
+          background_color_i: 0,
         }))
       }
       _ => panic!("Render started multiple times"),
@@ -278,6 +302,12 @@ impl ApplicationHandler for App {
                 // other reasons may include format changes
                 log::warn!("Failed to present to swapchain: Swapchain is out of date");
               }
+              FrameRenderError::Timeout => {
+                // the device may still be using every object, so the normal teardown would hang
+                // in device_wait_idle; exit without running any destructors instead
+                log::error!("{}", FrameRenderError::Timeout);
+                std::process::exit(1);
+              }
               other => {
                 log::error!(
                   "Rendering a frame returned an unrecoverable error\n{}",
@@ -355,6 +385,14 @@ impl ApplicationHandler for App {
                 status.threads_manager.screenshot();
               }
             }
+            KeyCode::F4 => {
+              if pressed && !repeating {
+                let color = BACKGROUND_COLOR_CYCLE[status.background_color_i];
+                status.background_color_i =
+                  (status.background_color_i + 1) % BACKGROUND_COLOR_CYCLE.len();
+                status.threads_manager.set_background_color(color);
+              }
+            }
             KeyCode::F3 | KeyCode::F10 => {
               if pressed && !repeating {
                 // attempt to resize the window to native resolution
@@ -406,6 +444,34 @@ fn main() -> Result<(), EventLoopError> {
 
   let event_loop = EventLoop::new().expect("Failed to initialize event loop");
 
+  // print all physical devices and whether they are supported, without starting the application
+  if std::env::args().any(|arg| arg == "--list-devices") {
+    let init = match PreWindowInit::new(&event_loop) {
+      Ok(v) => v,
+      Err(err) => {
+        log::error!("Failed to initialize Vulkan\n{}", err);
+        std::process::exit(1);
+      }
+    };
+    if let Err(err) = render::list_physical_devices(&init.instance) {
+      log::error!("Failed to enumerate physical devices\n{}", err);
+      std::process::exit(1);
+    }
+    return Ok(());
+  }
+
+  // use the device at the given index (as printed by --list-devices) instead of the best ranked one
+  let mut args = std::env::args();
+  if args.any(|arg| arg == "--device") {
+    match args.next().and_then(|arg| arg.parse().ok()) {
+      Some(index) => render::set_device_override(index),
+      None => {
+        log::error!("--device expects a device index, as printed by --list-devices");
+        std::process::exit(1);
+      }
+    }
+  }
+
   // make the event loop run continuously even if there is no new user input
   event_loop.set_control_flow(ControlFlow::Poll);
 
@@ -4,6 +4,8 @@ use ash::vk;
 use vkinitialization::device::SingleQueues;
 use vkobjects::{errors::OutOfMemoryError, utility, DeviceManuallyDestroyed};
 
+#[cfg(feature = "vl")]
+use crate::render::debug_labels::CommandLabels;
 use crate::{
   render::{
     compute::ComputeGPUData,
@@ -65,11 +67,14 @@ impl ComputeCommandBufferPool {
     write_to_cpu: bool,
 
     ferris_position: [f32; 2],
+    #[cfg(feature = "vl")] labels: &CommandLabels,
   ) -> Result<(), OutOfMemoryError> {
     let cb = self.cb;
     let begin_info =
       vk::CommandBufferBeginInfo::default().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
     device.begin_command_buffer(cb, &begin_info)?;
+    #[cfg(feature = "vl")]
+    labels.begin(cb, c"Compute frame");
 
     let new_particles_count = data.particles_copying;
     let push_constants = ComputePushConstants {
@@ -81,21 +86,23 @@ impl ComputeCommandBufferPool {
     let new_particles_size = new_particles_count as u64 * size_of::<Particle>() as u64;
 
     if new_particles_count > 0 {
-      if queues.transfer.family_index != queues.compute.family_index {
-        let acquire = vk::BufferMemoryBarrier2 {
-          src_access_mask: vk::AccessFlags2::empty(), // ownership acquire
-          dst_access_mask: vk::AccessFlags2::SHADER_READ,
-          src_stage_mask: vk::PipelineStageFlags2::empty(), // ownership acquire
-          dst_stage_mask: vk::PipelineStageFlags2::COMPUTE_SHADER,
-          src_queue_family_index: queues.transfer.family_index,
-          dst_queue_family_index: queues.compute.family_index,
-          buffer: data.particles_new,
-          offset: 0,
-          size: new_particles_size,
-          ..Default::default()
-        };
+      let transfers_ownership = queues.transfer.family_index != queues.compute.family_index
+        && ComputeGPUData::particles_new_shared_families(queues).is_none();
+      if transfers_ownership {
+        let acquire = super::BufferOwnershipTransfer::new(
+          queues.transfer.family_index,
+          queues.compute.family_index,
+          data.particles_new,
+          new_particles_size,
+        )
+        .acquire(
+          vk::AccessFlags2::SHADER_READ,
+          vk::PipelineStageFlags2::COMPUTE_SHADER,
+        );
         device.cmd_pipeline_barrier2(cb, &super::dependency_info(&[], &[acquire], &[]));
       } else {
+        // when shared concurrently with a different transfer family, the semaphore wait already
+        // orders the copy and this barrier is redundant
         let copy_wait = vk::BufferMemoryBarrier2 {
           src_access_mask: vk::AccessFlags2::TRANSFER_WRITE,
           dst_access_mask: vk::AccessFlags2::SHADER_READ,
@@ -208,18 +215,16 @@ impl ComputeCommandBufferPool {
       );
 
       if queues.compute.family_index != queues.graphics.family_index {
-        let release_to_graphics = vk::BufferMemoryBarrier2 {
-          src_access_mask: vk::AccessFlags2::TRANSFER_WRITE,
-          dst_access_mask: vk::AccessFlags2::empty(), // ownership release
-          src_stage_mask: vk::PipelineStageFlags2::COPY,
-          dst_stage_mask: vk::PipelineStageFlags2::empty(), // ownership release
-          src_queue_family_index: queues.compute.family_index,
-          dst_queue_family_index: queues.graphics.family_index,
-          buffer: data.particles_graphics[particle_buffer_i],
-          offset: 0,
-          size: cur_buffer_size,
-          ..Default::default()
-        };
+        let release_to_graphics = super::BufferOwnershipTransfer::new(
+          queues.compute.family_index,
+          queues.graphics.family_index,
+          data.particles_graphics[particle_buffer_i],
+          cur_buffer_size,
+        )
+        .release(
+          vk::AccessFlags2::TRANSFER_WRITE,
+          vk::PipelineStageFlags2::COPY,
+        );
         device.cmd_pipeline_barrier2(
           cb,
           &super::dependency_info(&[], &[release_to_graphics], &[]),
@@ -268,6 +273,8 @@ impl ComputeCommandBufferPool {
       );
     }
 
+    #[cfg(feature = "vl")]
+    labels.end(cb);
     device.end_command_buffer(cb)?;
     Ok(())
   }
@@ -3,6 +3,8 @@ use vkinitialization::device::SingleQueues;
 use vkobjects::{errors::OutOfMemoryError, DeviceManuallyDestroyed};
 
 use crate::render::compute::ComputeGPUData;
+#[cfg(feature = "vl")]
+use crate::render::debug_labels::CommandLabels;
 
 pub struct ComputeTransferCommandBufferPool {
   pool: vk::CommandPool,
@@ -50,11 +52,14 @@ impl ComputeTransferCommandBufferPool {
 
     data: &ComputeGPUData,
     new_particles_size: u64,
+    #[cfg(feature = "vl")] labels: &CommandLabels,
   ) -> Result<(), OutOfMemoryError> {
     let cb = self.copy_particles_new;
     let begin_info =
       vk::CommandBufferBeginInfo::default().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
     device.begin_command_buffer(cb, &begin_info)?;
+    #[cfg(feature = "vl")]
+    labels.begin(cb, c"Copy new particles");
 
     {
       let region = vk::BufferCopy {
@@ -66,22 +71,24 @@ impl ComputeTransferCommandBufferPool {
       device.cmd_copy_buffer(cb, data.from_cpu_read.buffer, data.particles_new, &[region]);
     }
 
-    if queues.transfer.family_index != queues.compute.family_index {
-      let release_to_compute = vk::BufferMemoryBarrier2 {
-        src_access_mask: vk::AccessFlags2::TRANSFER_WRITE,
-        dst_access_mask: vk::AccessFlags2::empty(), // ownership release
-        src_stage_mask: vk::PipelineStageFlags2::COPY,
-        dst_stage_mask: vk::PipelineStageFlags2::empty(), // ownership release
-        src_queue_family_index: queues.transfer.family_index,
-        dst_queue_family_index: queues.compute.family_index,
-        buffer: data.particles_new,
-        offset: 0,
-        size: new_particles_size,
-        ..Default::default()
-      };
+    let transfers_ownership = queues.transfer.family_index != queues.compute.family_index
+      && ComputeGPUData::particles_new_shared_families(queues).is_none();
+    if transfers_ownership {
+      let release_to_compute = super::BufferOwnershipTransfer::new(
+        queues.transfer.family_index,
+        queues.compute.family_index,
+        data.particles_new,
+        new_particles_size,
+      )
+      .release(
+        vk::AccessFlags2::TRANSFER_WRITE,
+        vk::PipelineStageFlags2::COPY,
+      );
       device.cmd_pipeline_barrier2(cb, &super::dependency_info(&[], &[release_to_compute], &[]));
     }
 
+    #[cfg(feature = "vl")]
+    labels.end(cb);
     device.end_command_buffer(cb)?;
     Ok(())
   }
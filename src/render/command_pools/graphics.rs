@@ -1,9 +1,11 @@
-use std::{cmp::Ordering, marker::PhantomData, ptr};
+use std::{cmp::Ordering, marker::PhantomData, ptr, time::Duration};
 
 use ash::vk;
 use vkinitialization::device::{QueueFamilies, SingleQueues};
-use vkobjects::{errors::OutOfMemoryError, utility, DeviceManuallyDestroyed};
+use vkobjects::{errors::OutOfMemoryError, utility, utility::OnErr, DeviceManuallyDestroyed};
 
+#[cfg(feature = "vl")]
+use crate::render::debug_labels::CommandLabels;
 use crate::{
   render::{
     compute::ParticlesDraw,
@@ -13,7 +15,7 @@ use crate::{
     vertices::QUAD_INDICES,
     RENDER_EXTENT,
   },
-  BACKGROUND_COLOR, OUT_OF_BOUNDS_AREA_COLOR, RESOLUTION,
+  OUT_OF_BOUNDS_AREA_COLOR, RESOLUTION,
 };
 
 use super::dependency_info;
@@ -21,12 +23,21 @@ use super::dependency_info;
 pub struct GraphicsCommandBufferPool {
   pool: vk::CommandPool,
   pub main: vk::CommandBuffer,
+
+  // frame start, render pass end, frame end
+  timestamps: Option<vk::QueryPool>,
+  timestamp_mask: u64,
+  timestamps_written: bool,
 }
 
 impl GraphicsCommandBufferPool {
+  const TIMESTAMP_COUNT: u32 = 3;
+
+  // timestamp_valid_bits of the graphics queue family, timestamps are disabled if 0
   pub fn create(
     device: &ash::Device,
     queue_families: &QueueFamilies,
+    timestamp_valid_bits: u32,
     #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
   ) -> Result<Self, OutOfMemoryError> {
     let flags = vk::CommandPoolCreateFlags::TRANSIENT;
@@ -50,9 +61,55 @@ impl GraphicsCommandBufferPool {
       marker,
       #[cfg(feature = "vl")]
       &command_buffer_names,
-    )?[0];
+    )
+    .on_err(|_| unsafe { pool.destroy_self(device) })?[0];
+
+    let timestamps = if timestamp_valid_bits > 0 {
+      let create_info = vk::QueryPoolCreateInfo::default()
+        .query_type(vk::QueryType::TIMESTAMP)
+        .query_count(Self::TIMESTAMP_COUNT);
+      Some(
+        unsafe { device.create_query_pool(&create_info, None) }
+          .map_err(OutOfMemoryError::from)
+          .on_err(|_| unsafe { pool.destroy_self(device) })?,
+      )
+    } else {
+      None
+    };
+    let timestamp_mask = if timestamp_valid_bits >= 64 {
+      u64::MAX
+    } else {
+      (1 << timestamp_valid_bits) - 1
+    };
+
+    Ok(Self {
+      pool,
+      main,
+      timestamps,
+      timestamp_mask,
+      timestamps_written: false,
+    })
+  }
+
+  // durations of the render pass and of everything after it (copies / blit to the swapchain)
+  // safety: the last recorded command buffer should have finished executing
+  pub unsafe fn last_timings(
+    &self,
+    device: &ash::Device,
+    timestamp_period: f32,
+  ) -> Option<(Duration, Duration)> {
+    // queries can't be read before being reset for the first time
+    let timestamps = self.timestamps.filter(|_| self.timestamps_written)?;
+    let mut data = [0u64; Self::TIMESTAMP_COUNT as usize];
+    device
+      .get_query_pool_results(timestamps, 0, &mut data, vk::QueryResultFlags::TYPE_64)
+      .ok()?;
 
-    Ok(Self { pool, main })
+    let to_duration = |start: u64, end: u64| {
+      let ticks = end.wrapping_sub(start) & self.timestamp_mask;
+      Duration::from_nanos((ticks as f64 * timestamp_period as f64) as u64)
+    };
+    Some((to_duration(data[0], data[1]), to_duration(data[1], data[2])))
   }
 
   pub unsafe fn reset(&mut self, device: &ash::Device) -> Result<(), OutOfMemoryError> {
@@ -79,12 +136,22 @@ impl GraphicsCommandBufferPool {
     data: &GPUData,
     particles_draw: ParticlesDraw,
 
+    background_color: vk::ClearColorValue,
     screenshot_buffer: Option<vk::Buffer>,
+    #[cfg(feature = "vl")] labels: &CommandLabels,
   ) -> Result<(), OutOfMemoryError> {
     let cb = self.main;
     let begin_info =
       vk::CommandBufferBeginInfo::default().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
     device.begin_command_buffer(cb, &begin_info)?;
+    #[cfg(feature = "vl")]
+    labels.begin(cb, c"Graphics frame");
+
+    if let Some(timestamps) = self.timestamps {
+      device.cmd_reset_query_pool(cb, timestamps, 0, Self::TIMESTAMP_COUNT);
+      device.cmd_write_timestamp2(cb, vk::PipelineStageFlags2::TOP_OF_PIPE, timestamps, 0);
+      self.timestamps_written = true;
+    }
 
     let render_width = RENDER_EXTENT.width as i32;
     let render_height = RENDER_EXTENT.height as i32;
@@ -97,18 +164,16 @@ impl GraphicsCommandBufferPool {
 
     {
       if queues.graphics.family_index != queues.compute.family_index {
-        let acquire = vk::BufferMemoryBarrier2 {
-          src_access_mask: vk::AccessFlags2::empty(), // ownership acquire
-          dst_access_mask: vk::AccessFlags2::VERTEX_ATTRIBUTE_READ,
-          src_stage_mask: vk::PipelineStageFlags2::empty(), // ownership acquire
-          dst_stage_mask: vk::PipelineStageFlags2::VERTEX_ATTRIBUTE_INPUT,
-          src_queue_family_index: queues.compute.family_index,
-          dst_queue_family_index: queues.graphics.family_index,
-          buffer: particles_draw.buffer,
-          offset: 0,
-          size: particles_draw.buffer_size,
-          ..Default::default()
-        };
+        let acquire = super::BufferOwnershipTransfer::new(
+          queues.compute.family_index,
+          queues.graphics.family_index,
+          particles_draw.buffer,
+          particles_draw.buffer_size,
+        )
+        .acquire(
+          vk::AccessFlags2::VERTEX_ATTRIBUTE_READ,
+          vk::PipelineStageFlags2::VERTEX_ATTRIBUTE_INPUT,
+        );
         device.cmd_pipeline_barrier2(cb, &super::dependency_info(&[], &[acquire], &[]));
       } else {
         let copy_wait = vk::BufferMemoryBarrier2 {
@@ -130,7 +195,7 @@ impl GraphicsCommandBufferPool {
     // in this case the render pass takes care of all internal queue synchronization
     {
       let clear_value = vk::ClearValue {
-        color: BACKGROUND_COLOR,
+        color: background_color,
       };
       let render_pass_begin_info = vk::RenderPassBeginInfo {
         s_type: vk::StructureType::RENDER_PASS_BEGIN_INFO,
@@ -146,6 +211,8 @@ impl GraphicsCommandBufferPool {
         p_clear_values: &clear_value,
         _marker: PhantomData,
       };
+      #[cfg(feature = "vl")]
+      labels.begin(cb, c"Render pass");
       device.cmd_begin_render_pass(cb, &render_pass_begin_info, vk::SubpassContents::INLINE);
 
       device.cmd_bind_descriptor_sets(
@@ -172,6 +239,12 @@ impl GraphicsCommandBufferPool {
       device.cmd_draw_indexed(cb, QUAD_INDICES.len() as u32, particles_draw.count, 0, 0, 0);
 
       device.cmd_end_render_pass(cb);
+      #[cfg(feature = "vl")]
+      labels.end(cb);
+
+      if let Some(timestamps) = self.timestamps {
+        device.cmd_write_timestamp2(cb, vk::PipelineStageFlags2::ALL_COMMANDS, timestamps, 1);
+      }
     }
 
     // 1 mip_level / 1 array layer
@@ -366,6 +439,12 @@ impl GraphicsCommandBufferPool {
       );
     }
 
+    if let Some(timestamps) = self.timestamps {
+      device.cmd_write_timestamp2(cb, vk::PipelineStageFlags2::ALL_COMMANDS, timestamps, 2);
+    }
+
+    #[cfg(feature = "vl")]
+    labels.end(cb);
     device.end_command_buffer(cb)?;
     Ok(())
   }
@@ -373,6 +452,9 @@ impl GraphicsCommandBufferPool {
 
 impl DeviceManuallyDestroyed for GraphicsCommandBufferPool {
   unsafe fn destroy_self(&self, device: &ash::Device) {
+    if let Some(timestamps) = self.timestamps {
+      device.destroy_query_pool(timestamps, None);
+    }
     device.destroy_command_pool(self.pool, None);
   }
 }
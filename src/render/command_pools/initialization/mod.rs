@@ -26,6 +26,21 @@ pub struct PendingInitialization {
 }
 
 impl PendingInitialization {
+  // polls the submission without blocking
+  pub unsafe fn is_finished(&self, device: &ash::Device) -> Result<bool, QueueSubmitError> {
+    Ok(device.get_fence_status(self.fence)?)
+  }
+
+  // returns false if timeout (in nanoseconds) has passed
+  // in that case the command buffer may still be executing and nothing can be destroyed yet
+  pub unsafe fn wait(&self, device: &ash::Device, timeout: u64) -> Result<bool, QueueSubmitError> {
+    match device.wait_for_fences(&[self.fence], true, timeout) {
+      Ok(()) => Ok(true),
+      Err(vk::Result::TIMEOUT) => Ok(false),
+      Err(err) => Err(err.into()),
+    }
+  }
+
   pub unsafe fn wait_and_self_destroy(&self, device: &ash::Device) -> Result<(), QueueSubmitError> {
     device.wait_for_fences(&[self.fence], true, u64::MAX)?;
 
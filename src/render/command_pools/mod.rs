@@ -105,3 +105,110 @@ fn dependency_info<'a>(
     _marker: PhantomData,
   }
 }
+
+// queue family ownership transfer of the start of a buffer
+// the release (recorded on the src queue) and the acquire (recorded on the dst queue) must use
+// the same families and range, so both barriers are built from the same value
+#[derive(Clone, Copy)]
+struct BufferOwnershipTransfer {
+  src_family_index: u32,
+  dst_family_index: u32,
+  buffer: vk::Buffer,
+  size: u64,
+}
+
+impl BufferOwnershipTransfer {
+  fn new(src_family_index: u32, dst_family_index: u32, buffer: vk::Buffer, size: u64) -> Self {
+    debug_assert_ne!(
+      src_family_index, dst_family_index,
+      "ownership transfers are only needed between different queue families"
+    );
+    Self {
+      src_family_index,
+      dst_family_index,
+      buffer,
+      size,
+    }
+  }
+
+  // src_* describe the last accesses on the src queue
+  fn release<'a>(
+    &self,
+    src_access_mask: vk::AccessFlags2,
+    src_stage_mask: vk::PipelineStageFlags2,
+  ) -> vk::BufferMemoryBarrier2<'a> {
+    vk::BufferMemoryBarrier2 {
+      src_access_mask,
+      dst_access_mask: vk::AccessFlags2::empty(), // ownership release
+      src_stage_mask,
+      dst_stage_mask: vk::PipelineStageFlags2::empty(), // ownership release
+      src_queue_family_index: self.src_family_index,
+      dst_queue_family_index: self.dst_family_index,
+      buffer: self.buffer,
+      offset: 0,
+      size: self.size,
+      ..Default::default()
+    }
+  }
+
+  // dst_* describe the first accesses on the dst queue
+  fn acquire<'a>(
+    &self,
+    dst_access_mask: vk::AccessFlags2,
+    dst_stage_mask: vk::PipelineStageFlags2,
+  ) -> vk::BufferMemoryBarrier2<'a> {
+    vk::BufferMemoryBarrier2 {
+      src_access_mask: vk::AccessFlags2::empty(), // ownership acquire
+      dst_access_mask,
+      src_stage_mask: vk::PipelineStageFlags2::empty(), // ownership acquire
+      dst_stage_mask,
+      src_queue_family_index: self.src_family_index,
+      dst_queue_family_index: self.dst_family_index,
+      buffer: self.buffer,
+      offset: 0,
+      size: self.size,
+      ..Default::default()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ash::vk::{self, Handle};
+
+  use super::BufferOwnershipTransfer;
+
+  #[test]
+  fn release_and_acquire_match() {
+    let transfer = BufferOwnershipTransfer::new(2, 0, vk::Buffer::from_raw(0x1234), 256);
+    let release = transfer.release(
+      vk::AccessFlags2::TRANSFER_WRITE,
+      vk::PipelineStageFlags2::COPY,
+    );
+    let acquire = transfer.acquire(
+      vk::AccessFlags2::VERTEX_ATTRIBUTE_READ,
+      vk::PipelineStageFlags2::VERTEX_ATTRIBUTE_INPUT,
+    );
+
+    assert_eq!(release.src_queue_family_index, 2);
+    assert_eq!(release.dst_queue_family_index, 0);
+    assert_eq!(
+      release.src_queue_family_index,
+      acquire.src_queue_family_index
+    );
+    assert_eq!(
+      release.dst_queue_family_index,
+      acquire.dst_queue_family_index
+    );
+    assert_eq!(release.buffer, acquire.buffer);
+    assert_eq!(
+      (release.offset, release.size),
+      (acquire.offset, acquire.size)
+    );
+    assert_eq!((release.offset, release.size), (0, 256));
+
+    // each half leaves the other queue's side of the dependency empty
+    assert!(release.dst_access_mask.is_empty() && release.dst_stage_mask.is_empty());
+    assert!(acquire.src_access_mask.is_empty() && acquire.src_stage_mask.is_empty());
+  }
+}
@@ -2,17 +2,20 @@ use std::ops::BitOr;
 
 use ash::vk;
 use rand::RngExt;
-use vkinitialization::device::{Device, PhysicalDevice};
+use vkinitialization::device::{Device, PhysicalDevice, SingleQueues};
 use vkobjects::{errors::OutOfMemoryError, utility::OnErr, DeviceManuallyDestroyed};
 
 use vkallocator::{DetailedMemory, HostMemorySyncError, MappedHostBuffer};
 
 use crate::{
   render::{
-    compute::ParticleBuffers, create_objs::create_buffer, errors::GPUDataAllocationError,
-    vertices::Particle, COMPUTE_FRAMES_IN_FLIGHT,
+    compute::ParticleBuffers,
+    create_objs::{create_buffer, create_buffer_shared},
+    errors::GPUDataAllocationError,
+    vertices::Particle,
+    COMPUTE_FRAMES_IN_FLIGHT,
   },
-  RESOLUTION,
+  RESOLUTION, SHARE_NEW_PARTICLES_CONCURRENTLY,
 };
 
 #[derive(Debug)]
@@ -57,8 +60,17 @@ impl ComputeGPUData {
   pub const INITIAL_CAPACITY: usize = 32000;
   pub const INITIAL_SIZE: u64 = (Self::INITIAL_CAPACITY * size_of::<Particle>()) as u64;
 
+  // families that access particles_new concurrently, or None if it belongs to one family at a
+  // time and its ownership is transferred from transfer to compute after each copy
+  pub fn particles_new_shared_families(queues: &SingleQueues) -> Option<[u32; 2]> {
+    let transfer_i = queues.transfer.family_index;
+    let compute_i = queues.compute.family_index;
+    (SHARE_NEW_PARTICLES_CONCURRENTLY && transfer_i != compute_i).then_some([transfer_i, compute_i])
+  }
+
   fn create_buffers(
     device: &Device,
+    queues: &SingleQueues,
     #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
   ) -> Result<Buffers, OutOfMemoryError> {
     let particles_compute_0 = create_buffer(
@@ -86,12 +98,16 @@ impl ComputeGPUData {
     .on_err(|_| unsafe { particles_compute_0.destroy_self(device) })?;
     let particles_compute = [particles_compute_0, particles_compute_1];
 
-    let particles_new = create_buffer(
+    let shared_families = Self::particles_new_shared_families(queues);
+    let particles_new = create_buffer_shared(
       device,
       Self::INITIAL_SIZE,
       vk::BufferUsageFlags::STORAGE_BUFFER
         .bitor(vk::BufferUsageFlags::TRANSFER_DST)
         .bitor(vk::BufferUsageFlags::TRANSFER_SRC),
+      shared_families
+        .as_ref()
+        .map_or(&[][..], |families| &families[..]),
       #[cfg(feature = "vl")]
       marker,
       #[cfg(feature = "vl")]
@@ -138,12 +154,14 @@ impl ComputeGPUData {
   pub fn new(
     device: &Device,
     physical_device: &PhysicalDevice,
+    queues: &SingleQueues,
     // gets owned by this struct
     particles_graphics: [vk::Buffer; ParticleBuffers::BUFFER_COUNT],
     #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
   ) -> Result<Self, GPUDataAllocationError> {
     let buffers = Self::create_buffers(
       device,
+      queues,
       #[cfg(feature = "vl")]
       marker,
     )
@@ -18,6 +18,8 @@ use std::{
 pub use sync_renderer::ComputeSyncRenderer;
 use vkinitialization::device::{Device, PhysicalDevice, SingleQueues};
 
+#[cfg(feature = "vl")]
+use crate::render::debug_labels::CommandLabels;
 use crate::{
   last_frames_durations::LastFramesDurations,
   render::{compute::sync_renderer::ComputeFrameRenderError, InitializationError},
@@ -58,6 +60,7 @@ pub fn start_compute(
   physical_device: PhysicalDevice,
   queues: SingleQueues,
   #[cfg(feature = "vl")] marker: vkinitialization::DebugUtilsMarker,
+  #[cfg(feature = "vl")] command_labels: CommandLabels,
 ) -> Result<ComputeThread, OutOfMemoryError> {
   let (data_sender, data_receiver) = mpsc::sync_channel(1);
   // events from compute queue
@@ -82,6 +85,8 @@ pub fn start_compute(
       data_sender,
       particle_buffers_to_compute,
       &marker,
+      #[cfg(feature = "vl")]
+      command_labels,
     ) {
       Ok(v) => {
         if let Err(_err) =
@@ -143,6 +148,12 @@ pub fn start_compute(
             log::error!("Main thread disconnected");
             break;
           }
+          ComputeFrameRenderError::Timeout => {
+            // dropping the renderer (here or in the main thread) would wait for the device to
+            // become idle, which would hang; exit without running any destructors instead
+            log::error!("{}", ComputeFrameRenderError::Timeout);
+            std::process::exit(1);
+          }
           err => {
             log::error!("{}", err);
             break;
@@ -5,6 +5,8 @@ use vkobjects::{
   DeviceManuallyDestroyed, ManuallyDestroyed,
 };
 
+#[cfg(feature = "vl")]
+use crate::render::debug_labels::CommandLabels;
 use crate::render::{
   command_pools::{ComputeCommandBufferPool, ComputeTransferCommandBufferPool},
   compute::{gpu_data::ComputeGPUData, ParticleBuffers},
@@ -24,6 +26,9 @@ pub struct ComputeRenderer {
 
   pub transfer_pool: ComputeTransferCommandBufferPool,
   pub command_pools: [ComputeCommandBufferPool; COMPUTE_FRAMES_IN_FLIGHT],
+
+  #[cfg(feature = "vl")]
+  pub command_labels: CommandLabels,
 }
 
 impl ComputeRenderer {
@@ -33,8 +38,10 @@ impl ComputeRenderer {
     queues: SingleQueues,
     particle_buffers: [vk::Buffer; ParticleBuffers::BUFFER_COUNT],
     #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
+    #[cfg(feature = "vl")] command_labels: CommandLabels,
   ) -> Result<Self, InitializationError> {
-    let gpu_data = ComputeGPUData::new(&device, &physical_device, particle_buffers, marker)?;
+    let gpu_data =
+      ComputeGPUData::new(&device, &physical_device, &queues, particle_buffers, marker)?;
     let descriptor_pool = ComputeDescriptorPool::new(&device).on_err(|_err| unsafe {
       gpu_data.destroy_self(&device);
     })?;
@@ -88,6 +95,8 @@ impl ComputeRenderer {
       pipeline,
       transfer_pool,
       command_pools,
+      #[cfg(feature = "vl")]
+      command_labels,
     })
   }
 
@@ -111,6 +120,8 @@ impl ComputeRenderer {
       particle_buffer_i,
       write_to_cpu,
       ferris_position,
+      #[cfg(feature = "vl")]
+      &self.command_labels,
     )?;
     Ok(())
   }
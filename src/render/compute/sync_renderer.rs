@@ -8,18 +8,21 @@ use vkobjects::{
 use winit::dpi::PhysicalSize;
 
 use crate::{
+  destructor::Owned,
   render::{
     compute::{
       particle_buffers::ParticleManager, renderer::ComputeRenderer, ComputeFrameResult,
       ComputeGPUData, ParticleBuffers, ParticlesDraw,
     },
     create_objs::{create_fence, create_semaphore},
-    InitializationError, COMPUTE_FRAMES_IN_FLIGHT, RENDER_EXTENT,
+    InitializationError, COMPUTE_FRAMES_IN_FLIGHT, FRAME_WAIT_TIMEOUT, RENDER_EXTENT,
   },
   RESOLUTION,
 };
 
 use super::ferris::Ferris;
+#[cfg(feature = "vl")]
+use crate::render::debug_labels::CommandLabels;
 
 pub struct ComputeSyncRenderer {
   tick_i: usize,
@@ -50,6 +53,11 @@ pub enum ComputeFrameRenderError {
 
   #[error("Device is lost")]
   DeviceLost,
+  #[error(
+    "Timed out waiting for a previous compute frame to finish on the device \
+    (enabling the vl feature may show what caused it)"
+  )]
+  Timeout,
 }
 
 impl From<vk::Result> for ComputeFrameRenderError {
@@ -59,6 +67,7 @@ impl From<vk::Result> for ComputeFrameRenderError {
         ComputeFrameRenderError::OutOfMemory(OutOfMemoryError::from(value))
       }
       vk::Result::ERROR_DEVICE_LOST => ComputeFrameRenderError::DeviceLost,
+      vk::Result::TIMEOUT => ComputeFrameRenderError::Timeout,
       _ => panic!("Invalid cast from vk::Result to ComputeFrameRenderError"),
     }
   }
@@ -72,10 +81,10 @@ impl ComputeSyncRenderer {
     compute_result_sender: mpsc::SyncSender<ComputeFrameResult>,
     particle_buffers: ParticleBuffers,
     #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
+    #[cfg(feature = "vl")] command_labels: CommandLabels,
   ) -> Result<Self, InitializationError> {
     let ferris = Ferris::new([500.0, 400.0]);
 
-    // todo: write all on errors
     let mut renderer = ComputeRenderer::new(
       device,
       physical_device,
@@ -83,44 +92,86 @@ impl ComputeSyncRenderer {
       particle_buffers.buffers,
       #[cfg(feature = "vl")]
       marker,
-    )?;
-
-    let transfer_finished = create_semaphore(
-      &renderer.device,
-      #[cfg(feature = "vl")]
-      marker,
-      #[cfg(feature = "vl")]
-      c"Compute transfer",
-    )?;
-    let frame0 = create_fence(
-      &renderer.device,
-      vk::FenceCreateFlags::SIGNALED,
-      #[cfg(feature = "vl")]
-      marker,
       #[cfg(feature = "vl")]
-      c"Compute main 0",
+      command_labels,
     )?;
-    let frame1 = create_fence(
-      &renderer.device,
-      vk::FenceCreateFlags::SIGNALED,
+
+    let (transfer_finished, frame_fences) = Self::create_sync_objects_and_submit_initial_data(
+      &mut renderer,
+      &queues,
       #[cfg(feature = "vl")]
       marker,
-      #[cfg(feature = "vl")]
-      c"Compute main 1",
-    )?;
-    let frame_fences = [frame0, frame1];
+    )
+    .on_err(|_| unsafe { ManuallyDestroyed::destroy_self(&renderer) })?;
+
+    let particle_manager = ParticleManager::new(particle_buffers.in_use_by_graphics);
+
+    Ok(Self {
+      tick_i: 0,
+      renderer,
+      ferris,
+      compute_result_sender,
+      transfer_finished,
+      frame_fences,
+      last_write_i: COMPUTE_FRAMES_IN_FLIGHT - 1,
+      save_gpu_contents_next_frame: true,
+      saving_gpu_contents: None,
+      particle_manager,
+    })
+  }
+
+  fn create_sync_objects_and_submit_initial_data(
+    renderer: &mut ComputeRenderer,
+    queues: &SingleQueues,
+    #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
+  ) -> Result<(vk::Semaphore, [vk::Fence; COMPUTE_FRAMES_IN_FLIGHT]), InitializationError> {
+    let device: &ash::Device = &renderer.device;
+    let transfer_finished = Owned::new(
+      device,
+      create_semaphore(
+        device,
+        #[cfg(feature = "vl")]
+        marker,
+        #[cfg(feature = "vl")]
+        c"Compute transfer",
+      )?,
+    );
+    let frame0 = Owned::new(
+      device,
+      create_fence(
+        device,
+        vk::FenceCreateFlags::SIGNALED,
+        #[cfg(feature = "vl")]
+        marker,
+        #[cfg(feature = "vl")]
+        c"Compute main 0",
+      )?,
+    );
+    let frame1 = Owned::new(
+      device,
+      create_fence(
+        device,
+        vk::FenceCreateFlags::SIGNALED,
+        #[cfg(feature = "vl")]
+        marker,
+        #[cfg(feature = "vl")]
+        c"Compute main 1",
+      )?,
+    );
 
     // write initial data to gpu_data
     renderer
       .gpu_data
-      .write_particles_to_from_cpu_read(&renderer.device, ComputeGPUData::INITIAL_CAPACITY)?;
+      .write_particles_to_from_cpu_read(device, ComputeGPUData::INITIAL_CAPACITY)?;
 
     unsafe {
       renderer.transfer_pool.record_copy_particles_new(
-        &renderer.device,
-        &queues,
+        device,
+        queues,
         &renderer.gpu_data,
         renderer.gpu_data.current_new_particles_size(),
+        #[cfg(feature = "vl")]
+        &renderer.command_labels,
       )?;
       let submit_info = vk::SubmitInfo {
         wait_semaphore_count: 0,
@@ -129,29 +180,17 @@ impl ComputeSyncRenderer {
         command_buffer_count: 1,
         p_command_buffers: &renderer.transfer_pool.copy_particles_new,
         signal_semaphore_count: 1,
-        p_signal_semaphores: &transfer_finished,
+        p_signal_semaphores: &*transfer_finished,
         ..Default::default()
       };
 
-      renderer
-        .device
-        .queue_submit(queues.transfer.handle, &[submit_info], vk::Fence::null())?;
+      device.queue_submit(queues.transfer.handle, &[submit_info], vk::Fence::null())?;
     }
 
-    let particle_manager = ParticleManager::new(particle_buffers.in_use_by_graphics);
-
-    Ok(Self {
-      tick_i: 0,
-      renderer,
-      ferris,
-      compute_result_sender,
-      transfer_finished,
-      frame_fences,
-      last_write_i: COMPUTE_FRAMES_IN_FLIGHT - 1,
-      save_gpu_contents_next_frame: true,
-      saving_gpu_contents: None,
-      particle_manager,
-    })
+    Ok((
+      transfer_finished.into_inner(),
+      [frame0.into_inner(), frame1.into_inner()],
+    ))
   }
 
   pub fn next_compute_frame(
@@ -176,10 +215,11 @@ impl ComputeSyncRenderer {
 
     // wait for frame of the same set (that holds current frame resources) to finish rendering
     unsafe {
-      self
-        .renderer
-        .device
-        .wait_for_fences(&[self.frame_fences[cur_write_i]], true, u64::MAX)?;
+      self.renderer.device.wait_for_fences(
+        &[self.frame_fences[cur_write_i]],
+        true,
+        FRAME_WAIT_TIMEOUT,
+      )?;
     }
     // particles buffer can be written to again even if it was written to last compute frame
     // (as long as it is not being used by graphics)
@@ -39,6 +39,17 @@ pub fn create_fence(
   }
 }
 
+// concurrent sharing if more than one queue family accesses the resource, which avoids
+// ownership transfers at the cost of some performance
+// queue_family_indices must not contain duplicates
+fn sharing_mode(queue_family_indices: &[u32]) -> vk::SharingMode {
+  if queue_family_indices.len() > 1 {
+    vk::SharingMode::CONCURRENT
+  } else {
+    vk::SharingMode::EXCLUSIVE
+  }
+}
+
 pub fn create_buffer(
   device: &ash::Device,
   size: u64,
@@ -46,15 +57,38 @@ pub fn create_buffer(
   #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
   #[cfg(feature = "vl")] name: &CStr,
 ) -> Result<vk::Buffer, OutOfMemoryError> {
+  create_buffer_shared(
+    device,
+    size,
+    usage,
+    &[],
+    #[cfg(feature = "vl")]
+    marker,
+    #[cfg(feature = "vl")]
+    name,
+  )
+}
+
+// buffer accessed concurrently by every family in queue_family_indices
+pub fn create_buffer_shared(
+  device: &ash::Device,
+  size: u64,
+  usage: vk::BufferUsageFlags,
+  queue_family_indices: &[u32],
+  #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
+  #[cfg(feature = "vl")] name: &CStr,
+) -> Result<vk::Buffer, OutOfMemoryError> {
+  let sharing_mode = sharing_mode(queue_family_indices);
   let create_info = vk::BufferCreateInfo {
     s_type: vk::StructureType::BUFFER_CREATE_INFO,
     p_next: ptr::null(),
     flags: vk::BufferCreateFlags::empty(),
     size,
     usage,
-    sharing_mode: vk::SharingMode::EXCLUSIVE,
-    queue_family_index_count: 0,
-    p_queue_family_indices: ptr::null(),
+    sharing_mode,
+    // ignored if sharing mode is exclusive
+    queue_family_index_count: queue_family_indices.len() as u32,
+    p_queue_family_indices: queue_family_indices.as_ptr(),
     _marker: PhantomData,
   };
   unsafe {
@@ -109,6 +143,8 @@ pub fn create_image_view(
   device: &ash::Device,
   image: vk::Image,
   format: vk::Format,
+  #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
+  #[cfg(feature = "vl")] name: &CStr,
 ) -> Result<vk::ImageView, OutOfMemoryError> {
   let create_info = vk::ImageViewCreateInfo {
     s_type: vk::StructureType::IMAGE_VIEW_CREATE_INFO,
@@ -134,8 +170,9 @@ pub fn create_image_view(
   };
 
   unsafe {
-    device
-      .create_image_view(&create_info, None)
-      .map_err(|err| err.into())
+    let image_view = device.create_image_view(&create_info, None)?;
+    #[cfg(feature = "vl")]
+    marker.set_obj_name(vk::ObjectType::IMAGE_VIEW, image_view.as_raw(), name)?;
+    Ok(image_view)
   }
 }
@@ -0,0 +1,28 @@
+use std::ffi::CStr;
+
+use ash::vk;
+
+// command buffer labels (VK_EXT_debug_utils), which group the recorded commands in tools like
+// RenderDoc and Nsight
+#[derive(Clone)]
+pub struct CommandLabels {
+  loader: ash::ext::debug_utils::Device,
+}
+
+impl CommandLabels {
+  pub fn new(instance: &ash::Instance, device: &ash::Device) -> Self {
+    Self {
+      loader: ash::ext::debug_utils::Device::new(instance, device),
+    }
+  }
+
+  // every begin has to be matched by an end in the same command buffer
+  pub unsafe fn begin(&self, cb: vk::CommandBuffer, name: &CStr) {
+    let label = vk::DebugUtilsLabelEXT::default().label_name(name);
+    unsafe { self.loader.cmd_begin_debug_utils_label(cb, &label) };
+  }
+
+  pub unsafe fn end(&self, cb: vk::CommandBuffer) {
+    unsafe { self.loader.cmd_end_debug_utils_label(cb) };
+  }
+}
@@ -27,6 +27,60 @@ pub fn error_chain_fmt(
   Ok(())
 }
 
+// one line explanation of a vk::Result, with a link to the spec for the most common failures
+pub fn describe_result(result: vk::Result) -> &'static str {
+  match result {
+    vk::Result::SUCCESS => "Command successfully completed",
+    vk::Result::NOT_READY => "A fence or query has not yet completed",
+    vk::Result::TIMEOUT => "A wait operation has not completed in the specified time",
+    vk::Result::INCOMPLETE => "A return array was too small for the result",
+    vk::Result::SUBOPTIMAL_KHR => {
+      "Swapchain no longer matches the surface properties exactly, but can still be presented to"
+    }
+    vk::Result::ERROR_OUT_OF_HOST_MEMORY => {
+      "A host memory allocation has failed \
+      (see https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#fundamentals-errorcodes)"
+    }
+    vk::Result::ERROR_OUT_OF_DEVICE_MEMORY => {
+      "A device memory allocation has failed \
+      (see https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#fundamentals-errorcodes)"
+    }
+    vk::Result::ERROR_INITIALIZATION_FAILED => {
+      "Initialization of an object could not be completed for implementation-specific reasons \
+      (validation layers may say more)"
+    }
+    vk::Result::ERROR_DEVICE_LOST => {
+      "The logical or physical device has been lost, usually due to an application or driver bug \
+      (see https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#devsandqueues-lost-device)"
+    }
+    vk::Result::ERROR_MEMORY_MAP_FAILED => "Mapping of a memory object has failed",
+    vk::Result::ERROR_LAYER_NOT_PRESENT => {
+      "A requested layer is not present or could not be loaded \
+      (see https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#extendingvulkan-layers)"
+    }
+    vk::Result::ERROR_EXTENSION_NOT_PRESENT => {
+      "A requested extension is not supported \
+      (see https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#extendingvulkan-extensions)"
+    }
+    vk::Result::ERROR_FEATURE_NOT_PRESENT => "A requested feature is not supported",
+    vk::Result::ERROR_INCOMPATIBLE_DRIVER => {
+      "The requested version of Vulkan is not supported by the driver or is otherwise incompatible"
+    }
+    vk::Result::ERROR_TOO_MANY_OBJECTS => "Too many objects of the type have already been created",
+    vk::Result::ERROR_FORMAT_NOT_SUPPORTED => "A requested format is not supported on this device",
+    vk::Result::ERROR_SURFACE_LOST_KHR => "A surface is no longer available",
+    vk::Result::ERROR_OUT_OF_DATE_KHR => {
+      "The surface has changed and the swapchain must be recreated before presenting again \
+      (see https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#wsi-swapchain)"
+    }
+    vk::Result::ERROR_NATIVE_WINDOW_IN_USE_KHR => {
+      "The window is already in use by Vulkan or another API"
+    }
+    vk::Result::ERROR_UNKNOWN => "An unknown error has occurred (validation layers may say more)",
+    _ => "Unexpected result (see https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkResult.html)",
+  }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum WindowError {
   #[error("OS error")]
@@ -54,8 +108,9 @@ pub enum InitializationError {
 
   #[error("An error occurred during device selection: {0}")]
   PhysicalDeviceSelectionError(#[from] PhysicalDeviceSelectionError),
-  #[error("No physical device supports the application")]
-  NoCompatibleDevices,
+  // each entry describes a device and why it was rejected
+  #[error("No physical device supports the application: {}", .0.join("; "))]
+  NoCompatibleDevices(Vec<String>),
   #[error("An error occurred during the creation of the logical device:\n    {0}")]
   DeviceCreationError(#[from] DeviceCreationError),
 
@@ -67,6 +122,8 @@ pub enum InitializationError {
 
   #[error("Failed to allocate device memory during initialization:\n    {0}")]
   AllocationError(#[from] GPUDataAllocationError),
+  #[error("Buffer of {got} bytes is too small, {needed} bytes are required")]
+  BufferTooSmall { needed: u64, got: u64 },
   #[error("Failed to flush contents to host buffer memory")]
   HostMemorySyncError(#[from] HostMemorySyncError),
 
@@ -84,11 +141,15 @@ pub enum InitializationError {
   #[error(transparent)]
   IOError(#[from] std::io::Error),
 
+  // objects used by the pending work are leaked, as they may still be in use by the device
+  #[error("Timed out waiting for initialization work to finish on the device")]
+  Timeout,
+
   // undefined behavior / driver or application bug (see vl)
   #[error(transparent)]
   DeviceIsLost(#[from] DeviceIsLost),
-  #[error("Vulkan returned ERROR_UNKNOWN")]
-  Unknown,
+  #[error("Vulkan returned {0}: {}", describe_result(*.0))]
+  Unknown(vk::Result),
 }
 impl std::fmt::Debug for InitializationError {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -115,15 +176,17 @@ impl From<vk::Result> for InitializationError {
         OutOfMemoryError::from(value).into()
       }
       vk::Result::ERROR_DEVICE_LOST => InitializationError::DeviceIsLost(DeviceIsLost {}),
-      vk::Result::ERROR_UNKNOWN => InitializationError::Unknown,
       // validation layers may say more on this
-      vk::Result::ERROR_INITIALIZATION_FAILED => InitializationError::Unknown,
+      vk::Result::ERROR_UNKNOWN | vk::Result::ERROR_INITIALIZATION_FAILED => {
+        InitializationError::Unknown(value)
+      }
       _ => {
         log::error!(
-          "Unhandled vk::Result {} during general initialization",
-          value
+          "Unhandled vk::Result {} during general initialization: {}",
+          value,
+          describe_result(value)
         );
-        InitializationError::Unknown
+        InitializationError::Unknown(value)
       }
     }
   }
@@ -139,6 +202,11 @@ pub enum FrameRenderError {
 
   #[error("Device is lost")]
   DeviceLost,
+  #[error(
+    "Timed out waiting for a previous frame to finish on the device \
+    (enabling the vl feature may show what caused it)"
+  )]
+  Timeout,
 
   #[error("Failed to acquire swapchain image: {0}")]
   FailedToAcquireSwapchainImage(#[from] AcquireNextImageError),
@@ -158,7 +226,12 @@ impl From<vk::Result> for FrameRenderError {
         FrameRenderError::OutOfMemory(OutOfMemoryError::from(value))
       }
       vk::Result::ERROR_DEVICE_LOST => FrameRenderError::DeviceLost,
-      _ => panic!("Invalid cast from vk::Result to FrameRenderError"),
+      vk::Result::TIMEOUT => FrameRenderError::Timeout,
+      _ => panic!(
+        "Invalid cast from vk::Result {} to FrameRenderError: {}",
+        value,
+        describe_result(value)
+      ),
     }
   }
 }
@@ -198,4 +271,10 @@ pub enum GPUDataAllocationError {
   OutOfMemory(#[from] OutOfMemoryError),
   #[error("Failed to submit allocation workload to a queue: {0}")]
   QueueSubmitError(#[from] QueueSubmitError),
+  #[error(
+    "Requested image of {}x{} exceeds the device limit of {max} pixels per dimension",
+    .requested.width,
+    .requested.height
+  )]
+  ImageTooLarge { requested: vk::Extent2D, max: u32 },
 }
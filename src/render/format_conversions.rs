@@ -7,6 +7,65 @@ pub const KNOWN_FORMATS: [vk::Format; 4] = [
   vk::Format::B8G8R8A8_UNORM,
 ];
 
+// size in bytes of a single texel of an uncompressed color format
+// None for compressed, depth/stencil and other formats that aren't handled
+pub fn format_texel_size(format: vk::Format) -> Option<u32> {
+  match format {
+    vk::Format::R8_UNORM
+    | vk::Format::R8_SNORM
+    | vk::Format::R8_UINT
+    | vk::Format::R8_SINT
+    | vk::Format::R8_SRGB => Some(1),
+    vk::Format::R8G8_UNORM
+    | vk::Format::R8G8_SNORM
+    | vk::Format::R8G8_UINT
+    | vk::Format::R8G8_SINT
+    | vk::Format::R8G8_SRGB
+    | vk::Format::R16_UNORM
+    | vk::Format::R16_SNORM
+    | vk::Format::R16_UINT
+    | vk::Format::R16_SINT
+    | vk::Format::R16_SFLOAT => Some(2),
+    vk::Format::R8G8B8_UNORM
+    | vk::Format::R8G8B8_SRGB
+    | vk::Format::B8G8R8_UNORM
+    | vk::Format::B8G8R8_SRGB => Some(3),
+    vk::Format::R8G8B8A8_UNORM
+    | vk::Format::R8G8B8A8_SNORM
+    | vk::Format::R8G8B8A8_UINT
+    | vk::Format::R8G8B8A8_SINT
+    | vk::Format::R8G8B8A8_SRGB
+    | vk::Format::B8G8R8A8_UNORM
+    | vk::Format::B8G8R8A8_SNORM
+    | vk::Format::B8G8R8A8_UINT
+    | vk::Format::B8G8R8A8_SINT
+    | vk::Format::B8G8R8A8_SRGB
+    | vk::Format::A2B10G10R10_UNORM_PACK32
+    | vk::Format::A2R10G10B10_UNORM_PACK32
+    | vk::Format::B10G11R11_UFLOAT_PACK32
+    | vk::Format::R16G16_UNORM
+    | vk::Format::R16G16_SFLOAT
+    | vk::Format::R32_UINT
+    | vk::Format::R32_SINT
+    | vk::Format::R32_SFLOAT => Some(4),
+    vk::Format::R16G16B16A16_UNORM
+    | vk::Format::R16G16B16A16_SNORM
+    | vk::Format::R16G16B16A16_UINT
+    | vk::Format::R16G16B16A16_SINT
+    | vk::Format::R16G16B16A16_SFLOAT
+    | vk::Format::R32G32_UINT
+    | vk::Format::R32G32_SINT
+    | vk::Format::R32G32_SFLOAT => Some(8),
+    vk::Format::R32G32B32_UINT | vk::Format::R32G32B32_SINT | vk::Format::R32G32B32_SFLOAT => {
+      Some(12)
+    }
+    vk::Format::R32G32B32A32_UINT
+    | vk::Format::R32G32B32A32_SINT
+    | vk::Format::R32G32B32A32_SFLOAT => Some(16),
+    _ => None,
+  }
+}
+
 fn convert_rgba_to_bgra(bytes: &mut [u8]) {
   let (chunks, remainder) = bytes.as_chunks_mut::<4>();
   assert!(remainder.is_empty());
@@ -24,3 +83,52 @@ pub fn convert_rgba_data_to_format(data: &mut [u8], target_format: vk::Format) {
     _ => panic!("Trying to convert to unsupported format"),
   }
 }
+
+// inverse of convert_rgba_data_to_format
+// returns false (leaving data untouched) if the channel order of source_format is not handled
+pub fn convert_format_data_to_rgba(data: &mut [u8], source_format: vk::Format) -> bool {
+  match source_format {
+    vk::Format::R8G8B8A8_SRGB | vk::Format::R8G8B8A8_UNORM => true,
+    vk::Format::B8G8R8A8_SRGB | vk::Format::B8G8R8A8_UNORM => {
+      convert_rgba_to_bgra(data); // the swap is its own inverse
+      true
+    }
+    _ => false,
+  }
+}
+
+// clear values for integer formats are not normalized, so they have to be scaled to the format range
+pub fn clear_color_from_normalized(color: [f32; 4], format: vk::Format) -> vk::ClearColorValue {
+  match format {
+    vk::Format::R8G8B8A8_UINT | vk::Format::B8G8R8A8_UINT => vk::ClearColorValue {
+      uint32: color.map(|c| (c.clamp(0.0, 1.0) * u8::MAX as f32).round() as u32),
+    },
+    vk::Format::R8G8B8A8_SINT | vk::Format::B8G8R8A8_SINT => vk::ClearColorValue {
+      int32: color.map(|c| (c.clamp(-1.0, 1.0) * i8::MAX as f32).round() as i32),
+    },
+    _ => vk::ClearColorValue { float32: color },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ash::vk;
+
+  use super::format_texel_size;
+
+  #[test]
+  fn texel_size_of_color_formats() {
+    assert_eq!(format_texel_size(vk::Format::R8_UNORM), Some(1));
+    assert_eq!(format_texel_size(vk::Format::R8G8B8A8_SRGB), Some(4));
+    assert_eq!(format_texel_size(vk::Format::B8G8R8A8_UNORM), Some(4));
+    assert_eq!(format_texel_size(vk::Format::R16G16B16A16_SFLOAT), Some(8));
+    assert_eq!(format_texel_size(vk::Format::R32G32B32A32_SFLOAT), Some(16));
+  }
+
+  #[test]
+  fn texel_size_of_unhandled_formats() {
+    assert_eq!(format_texel_size(vk::Format::BC1_RGBA_UNORM_BLOCK), None);
+    assert_eq!(format_texel_size(vk::Format::D32_SFLOAT), None);
+    assert_eq!(format_texel_size(vk::Format::UNDEFINED), None);
+  }
+}
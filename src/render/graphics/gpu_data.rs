@@ -1,10 +1,13 @@
 use std::ops::BitOr;
 
-use crate::render::{
-  command_pools::{self, initialization::PendingInitialization},
-  create_objs::{create_buffer, create_image, create_image_view},
-  errors::GPUDataAllocationError,
-  vertices::{QUAD_INDICES, QUAD_INDICES_SIZE, VERTICES, VERTICES_SIZE},
+use crate::{
+  destructor::Owned,
+  render::{
+    command_pools::{self, initialization::PendingInitialization},
+    create_objs::{create_buffer, create_image, create_image_view},
+    errors::GPUDataAllocationError,
+    vertices::{QUAD_INDICES, QUAD_INDICES_SIZE, VERTICES, VERTICES_SIZE},
+  },
 };
 use ash::vk;
 use vkinitialization::device::{Device, PhysicalDevice, SingleQueues};
@@ -44,6 +47,11 @@ pub struct PendingDataInitialization {
 }
 
 impl PendingDataInitialization {
+  // returns false if timeout (in nanoseconds) has passed, see PendingInitialization::wait
+  pub unsafe fn wait(&self, device: &ash::Device, timeout: u64) -> Result<bool, QueueSubmitError> {
+    self.command_buffer_submit.wait(device, timeout)
+  }
+
   // should not fail
   pub unsafe fn wait_and_self_destroy(&self, device: &ash::Device) -> Result<(), QueueSubmitError> {
     self.command_buffer_submit.wait_and_self_destroy(device)?;
@@ -141,70 +149,88 @@ impl GPUData {
     queues: &SingleQueues,
     #[cfg(feature = "vl")] marker: &vkinitialization::DebugUtilsMarker,
   ) -> Result<(Self, PendingDataInitialization), GPUDataAllocationError> {
-    let texture = create_image(
+    // fail early with a clear error instead of somewhere inside image creation or allocation
+    let max = physical_device.properties.p10.limits.max_image_dimension2_d;
+    if texture_extent.width > max || texture_extent.height > max {
+      return Err(GPUDataAllocationError::ImageTooLarge {
+        requested: texture_extent,
+        max,
+      });
+    }
+
+    let texture = Owned::new(
       device,
-      texture_format,
-      texture_extent.width,
-      texture_extent.height,
-      TEXTURE_USAGES,
-      #[cfg(feature = "vl")]
-      marker,
-      #[cfg(feature = "vl")]
-      c"Texture",
-    )?;
-    let vertex_buffer: vk::Buffer = create_buffer(
+      create_image(
+        device,
+        texture_format,
+        texture_extent.width,
+        texture_extent.height,
+        TEXTURE_USAGES,
+        #[cfg(feature = "vl")]
+        marker,
+        #[cfg(feature = "vl")]
+        c"Texture",
+      )?,
+    );
+    let vertex_buffer = Owned::new(
       device,
-      VERTICES_SIZE,
-      vk::BufferUsageFlags::VERTEX_BUFFER.bitor(vk::BufferUsageFlags::TRANSFER_DST),
-      #[cfg(feature = "vl")]
-      marker,
-      #[cfg(feature = "vl")]
-      c"Vertex buffer",
-    )
-    .on_err(|_| unsafe { texture.destroy_self(device) })?;
-    let index_buffer: vk::Buffer = create_buffer(
+      create_buffer(
+        device,
+        VERTICES_SIZE,
+        vk::BufferUsageFlags::VERTEX_BUFFER.bitor(vk::BufferUsageFlags::TRANSFER_DST),
+        #[cfg(feature = "vl")]
+        marker,
+        #[cfg(feature = "vl")]
+        c"Vertex buffer",
+      )?,
+    );
+    let index_buffer = Owned::new(
       device,
-      QUAD_INDICES_SIZE,
-      vk::BufferUsageFlags::INDEX_BUFFER.bitor(vk::BufferUsageFlags::TRANSFER_DST),
-      #[cfg(feature = "vl")]
-      marker,
-      #[cfg(feature = "vl")]
-      c"Index buffer",
-    )
-    .on_err(|_| unsafe { destroy!(device => &vertex_buffer, &texture) })?;
+      create_buffer(
+        device,
+        QUAD_INDICES_SIZE,
+        vk::BufferUsageFlags::INDEX_BUFFER.bitor(vk::BufferUsageFlags::TRANSFER_DST),
+        #[cfg(feature = "vl")]
+        marker,
+        #[cfg(feature = "vl")]
+        c"Index buffer",
+      )?,
+    );
 
-    let device_alloc = vkallocator::allocate_and_bind_memory(
+    let device_alloc = Owned::new(
       device,
-      physical_device,
-      [
-        vk::MemoryPropertyFlags::DEVICE_LOCAL,
-        vk::MemoryPropertyFlags::empty(),
-      ],
-      [&texture, &vertex_buffer, &index_buffer],
-      0.5,
-      false,
-      #[cfg(feature = "log_alloc")]
-      Some(["Target image", "Vertex buffer", "Index buffer"]),
-      #[cfg(feature = "log_alloc")]
-      "Constant data",
-    )
-    .on_err(|_| unsafe { destroy!(device => &texture, &index_buffer, &vertex_buffer) })?;
+      vkallocator::allocate_and_bind_memory(
+        device,
+        physical_device,
+        [
+          vk::MemoryPropertyFlags::DEVICE_LOCAL,
+          vk::MemoryPropertyFlags::empty(),
+        ],
+        [&*texture, &*vertex_buffer, &*index_buffer],
+        0.5,
+        false,
+        #[cfg(feature = "log_alloc")]
+        Some(["Target image", "Vertex buffer", "Index buffer"]),
+        #[cfg(feature = "log_alloc")]
+        "Constant data",
+      )?,
+    );
 
-    let pending_device_init = create_and_copy_from_staging_buffers(
+    let pending_device_init = Owned::new(
       device,
-      physical_device,
-      queues,
-      vertex_buffer,
-      index_buffer,
-      texture,
-      texture_extent,
-      texture_data,
-      #[cfg(feature = "vl")]
-      marker,
-    )
-    .on_err(|_| unsafe {
-      destroy!(device => &texture, &index_buffer, &vertex_buffer, &device_alloc)
-    })?;
+      create_and_copy_from_staging_buffers(
+        device,
+        physical_device,
+        queues,
+        *vertex_buffer,
+        *index_buffer,
+        *texture,
+        texture_extent,
+        texture_data,
+        #[cfg(feature = "vl")]
+        marker,
+      )?,
+    );
 
     const EXPECTED_MAX_MEM_COUNT: usize = 3;
     let mut memories = Vec::with_capacity(EXPECTED_MAX_MEM_COUNT);
@@ -217,18 +243,27 @@ impl GPUData {
     );
     log::info!("Allocated memory count: {}", memories.len());
 
-    let texture_view = create_image_view(device, texture, texture_format)
-    .on_err(|_| unsafe {destroy!(device => &pending_device_init, &texture, &index_buffer, &vertex_buffer, memories.as_slice()) })?;
+    let texture_view = create_image_view(
+      device,
+      *texture,
+      texture_format,
+      #[cfg(feature = "vl")]
+      marker,
+      #[cfg(feature = "vl")]
+      c"Texture view",
+    )?;
 
+    // all objects were created successfully, ownership is transferred to GPUData
+    device_alloc.into_inner();
     Ok((
       Self {
-        texture,
+        texture: texture.into_inner(),
         texture_view,
-        vertex_buffer,
-        index_buffer,
+        vertex_buffer: vertex_buffer.into_inner(),
+        index_buffer: index_buffer.into_inner(),
         memories,
       },
-      pending_device_init,
+      pending_device_init.into_inner(),
     ))
   }
 }
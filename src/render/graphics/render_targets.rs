@@ -79,9 +79,15 @@ impl RenderTargets {
 
     let image_views = fill_destroyable_array_from_iter!(
       device,
-      images
-        .iter()
-        .map(|image| create_image_view(device, *image, render_format)),
+      images.iter().map(|image| create_image_view(
+        device,
+        *image,
+        render_format,
+        #[cfg(feature = "vl")]
+        marker,
+        #[cfg(feature = "vl")]
+        c"Frame view"
+      )),
       GRAPHICS_FRAMES_IN_FLIGHT
     )
     .on_err(|_| unsafe { destroy!(device => images.as_ref(), &alloc) })?;
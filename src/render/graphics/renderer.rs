@@ -1,5 +1,6 @@
-use ash::vk;
+use std::{path::Path, time::Duration};
 
+use ash::vk;
 use vkobjects::{
   errors::OutOfMemoryError, fill_destroyable_array_with_expression, utility::OnErr,
   DeviceManuallyDestroyed, ManuallyDestroyed,
@@ -15,14 +16,14 @@ use crate::{
     format_conversions::{self, KNOWN_FORMATS},
     initialization,
     pipelines::{self, GraphicsPipeline},
-    InitializationError, PostWindowInit, GRAPHICS_FRAMES_IN_FLIGHT, RENDER_EXTENT,
-    SWAPCHAIN_IMAGE_USAGES,
+    InitializationError, PostWindowInit, GRAPHICS_FRAMES_IN_FLIGHT, INITIALIZATION_WAIT_TIMEOUT,
+    RENDER_EXTENT, SWAPCHAIN_IMAGE_USAGES,
   },
-  RESOLUTION, SCREENSHOT_SAVE_FILE,
+  BACKGROUND_COLOR, RESOLUTION,
 };
 
 use super::{
-  gpu_data::GPUData,
+  gpu_data::{GPUData, TEXTURE_FORMAT_FEATURES},
   render_pass::create_render_pass,
   render_targets::RenderTargets,
   screenshot_buffer::ScreenshotBuffer,
@@ -60,6 +61,8 @@ pub struct Renderer {
   descriptor_pool: DescriptorPool,
 
   screenshot_buffer: ScreenshotBuffer,
+
+  background_color: vk::ClearColorValue,
 }
 
 impl Renderer {
@@ -67,7 +70,7 @@ impl Renderer {
     post_window: PostWindowInit,
     particle_buffers: ParticleBuffers,
   ) -> Result<Self, InitializationError> {
-    let mut destructor: Destructor<11> = Destructor::new();
+    let mut destructor: Destructor<{ 9 + GRAPHICS_FRAMES_IN_FLIGHT }> = Destructor::new();
 
     let swapchains = Swapchains::new(
       &post_window.instance,
@@ -85,19 +88,20 @@ impl Renderer {
     })?;
     destructor.push(&swapchains);
 
+    // use the swapchain format for the texture if possible, otherwise the first supported format
     let swapchain_format = swapchains.get_format();
-    let texture_format = if KNOWN_FORMATS.contains(&swapchain_format) {
+    let texture_format = if KNOWN_FORMATS.contains(&swapchain_format)
+      && initialization::format_is_supported(
+        &post_window.instance,
+        *post_window.physical_device,
+        swapchain_format,
+        vk::ImageTiling::OPTIMAL,
+        TEXTURE_FORMAT_FEATURES,
+      ) {
       swapchain_format
     } else {
-      KNOWN_FORMATS
-        .into_iter()
-        .find(|&f| {
-          initialization::format_is_supported(
-            &post_window.instance,
-            *post_window.physical_device,
-            f,
-          )
-        })
+      // device selection rejects devices without a supported texture format
+      initialization::choose_texture_format(&post_window.instance, *post_window.physical_device)
         .unwrap()
     };
 
@@ -131,6 +135,28 @@ impl Renderer {
     // vkCmdCopyImage does not convert formats, while vkCmdBlitImage does, so using different formats
     // would mean not using vkCmdCopyImage at all anymore
     let render_format = swapchains.get_format();
+
+    // the screenshot buffer has to fit a whole render target
+    match format_conversions::format_texel_size(render_format) {
+      Some(texel_size) => {
+        let needed = RENDER_EXTENT.width as u64 * RENDER_EXTENT.height as u64 * texel_size as u64;
+        if needed > ScreenshotBuffer::BUFFER_SIZE {
+          unsafe {
+            destructor.fire(&post_window.device);
+            ManuallyDestroyed::destroy_self(&post_window);
+          }
+          return Err(InitializationError::BufferTooSmall {
+            needed,
+            got: ScreenshotBuffer::BUFFER_SIZE,
+          });
+        }
+      }
+      None => log::warn!(
+        "Unknown texel size for render format {:?}, screenshots may be invalid",
+        render_format
+      ),
+    }
+
     let render_pass =
       create_render_pass(&post_window.device, render_format).on_err(|_| unsafe {
         destructor.fire(&post_window.device);
@@ -177,24 +203,36 @@ impl Renderer {
     destructor.push(&descriptor_pool);
 
     log::debug!("Creating pipeline");
-    let graphics_pipeline = GraphicsPipeline::new(
-      &post_window.device,
-      pipeline_cache,
-      render_pass,
-      &descriptor_pool,
-      RENDER_EXTENT,
-    )
-    .on_err(|_| unsafe {
-      destructor.fire(&post_window.device);
-      ManuallyDestroyed::destroy_self(&post_window);
-    })?;
+    let graphics_pipeline =
+      pipelines::time_pipeline_creation("Graphics", created_from_file, || {
+        GraphicsPipeline::new(
+          &post_window.device,
+          pipeline_cache,
+          render_pass,
+          &descriptor_pool,
+          RENDER_EXTENT,
+        )
+      })
+      .on_err(|_| unsafe {
+        destructor.fire(&post_window.device);
+        ManuallyDestroyed::destroy_self(&post_window);
+      })?;
     destructor.push(&graphics_pipeline);
 
+    let graphics_family_properties = unsafe {
+      post_window
+        .instance
+        .get_physical_device_queue_family_properties(*post_window.physical_device)
+    }[post_window.physical_device.queue_families.graphics.index as usize];
+    if graphics_family_properties.timestamp_valid_bits == 0 {
+      log::info!("Graphics queue family doesn't support timestamps, frame timings are disabled");
+    }
     let command_pools = fill_destroyable_array_with_expression!(
       &post_window.device,
       GraphicsCommandBufferPool::create(
         &post_window.device,
         &post_window.physical_device.queue_families,
+        graphics_family_properties.timestamp_valid_bits,
         #[cfg(feature = "vl")]
         &post_window.debug_utils_marker
       ),
@@ -204,9 +242,31 @@ impl Renderer {
       destructor.fire(&post_window.device);
       ManuallyDestroyed::destroy_self(&post_window);
     })?;
-    destructor.push(command_pools.as_ptr());
+    for pool in command_pools.iter() {
+      destructor.push(pool);
+    }
 
     unsafe {
+      // the upload usually finishes while the objects above are being created
+      let mut finished = gpu_data_pending_initialization
+        .is_finished(&post_window.device)
+        .on_err(|_| {
+          destructor.fire(&post_window.device);
+          ManuallyDestroyed::destroy_self(&post_window);
+        })?;
+      if !finished {
+        log::debug!("Waiting for the initial data upload to finish");
+        finished = gpu_data_pending_initialization
+          .wait(&post_window.device, INITIALIZATION_WAIT_TIMEOUT)
+          .on_err(|_| {
+            destructor.fire(&post_window.device);
+            ManuallyDestroyed::destroy_self(&post_window);
+          })?;
+      }
+      if !finished {
+        // the transfer may still be executing, so none of the objects can be safely destroyed
+        return Err(InitializationError::Timeout);
+      }
       gpu_data_pending_initialization
         .wait_and_self_destroy(&post_window.device)
         .on_err(|_| {
@@ -214,6 +274,9 @@ impl Renderer {
           ManuallyDestroyed::destroy_self(&post_window);
         })?;
     }
+    // already destroyed, should not be destroyed again if something fails after this point
+    destructor.remove(&gpu_data_pending_initialization);
+
     let screenshot_buffer = ScreenshotBuffer::new(
       &post_window.device,
       &post_window.physical_device,
@@ -238,6 +301,7 @@ impl Renderer {
       render_targets,
       screenshot_buffer,
       particle_buffers,
+      background_color: BACKGROUND_COLOR,
     })
   }
 
@@ -261,11 +325,14 @@ impl Renderer {
       &self.descriptor_pool,
       &self.data,
       particles_draw,
+      self.background_color,
       if save_to_screenshot_buffer {
         Some(*self.screenshot_buffer.buffer)
       } else {
         None
       },
+      #[cfg(feature = "vl")]
+      &self.init.command_labels,
     )?;
     Ok(())
   }
@@ -390,44 +457,77 @@ impl Renderer {
       .destroy_old(&self.init.device, cur_total_frame);
   }
 
+  // GPU durations of the render pass and of the copies after it for the last frame recorded
+  // with frame_i, None if timestamps are not supported or not yet available
+  // safety: the frame should have finished executing
+  pub unsafe fn last_timings(&self, frame_i: usize) -> Option<(Duration, Duration)> {
+    let limits = &self.init.physical_device.properties.p10.limits;
+    self.command_pools[frame_i].last_timings(&self.init.device, limits.timestamp_period)
+  }
+
   pub fn render_format(&self) -> vk::Format {
     self.swapchains.get_format()
   }
 
+  // takes effect the next time a frame is recorded
+  pub fn set_background_color(&mut self, color: vk::ClearColorValue) {
+    self.background_color = color;
+  }
+
+  // color components in the [0, 1] range, converted according to the current render format
+  pub fn set_normalized_background_color(&mut self, color: [f32; 4]) {
+    self.set_background_color(format_conversions::clear_color_from_normalized(
+      color,
+      self.render_format(),
+    ));
+  }
+
   // safety: screenshot buffer should not be in use
-  pub fn save_screenshot_buffer_as_rgba8(
+  // raw texels of the last screenshot, tightly packed rows in the render format
+  pub fn read_screenshot_buffer(&self) -> Result<Vec<u8>, ImageError> {
+    let data = unsafe { self.screenshot_buffer.read_memory(&self.init.device) }?;
+
+    // the screenshot copy is densely packed (buffer_row_length = 0)
+    // the whole buffer is kept if the texel size is unknown, which is warned about on creation
+    let size = format_conversions::format_texel_size(self.render_format())
+      .map_or(data.len(), |texel_size| {
+        RESOLUTION[0] as usize * RESOLUTION[1] as usize * texel_size as usize
+      });
+    let mut pixels = data.into_vec();
+    pixels.truncate(size);
+    Ok(pixels)
+  }
+
+  // last screenshot with channels reordered to rgba8, regardless of the format it was rendered in
+  pub fn read_screenshot_buffer_as_rgba8(
     &self,
     saved_format: vk::Format,
-  ) -> Result<(), ImageError> {
-    let mut data = unsafe { self.screenshot_buffer.read_memory(&self.init.device) }?;
-
-    let (data_chunks, data_chunks_remainder) = data.as_chunks_mut::<4>();
-    assert!(data_chunks_remainder.is_empty());
+  ) -> Result<Vec<u8>, ImageError> {
+    let mut pixels = self.read_screenshot_buffer()?;
+    if !format_conversions::convert_format_data_to_rgba(&mut pixels, saved_format) {
+      log::error!(
+        "Attempting to read screenshot containing an unhandled format: \"{:?}\"",
+        saved_format
+      );
+    }
+    Ok(pixels)
+  }
 
+  pub fn save_screenshot_buffer_as_png(
+    &self,
+    saved_format: vk::Format,
+    path: &Path,
+  ) -> Result<(), ImageError> {
     // todo: make data save in a separate thread to not stall rendering
+    let pixels = self.read_screenshot_buffer_as_rgba8(saved_format)?;
 
-    // transform to rgba8
-    match saved_format {
-      vk::Format::R8G8B8A8_SRGB | vk::Format::R8G8B8A8_UNORM => {}
-      vk::Format::B8G8R8A8_SRGB | vk::Format::B8G8R8A8_UNORM => {
-        for pixel in data_chunks {
-          pixel.swap(0, 2); // swap B and R
-        }
-      }
-      _ => {
-        log::error!(
-          "Attempting to save screenshot containing an unhandled format: \"{:?}\"",
-          saved_format
-        );
-      }
-    }
-
-    image::save_buffer(
-      SCREENSHOT_SAVE_FILE,
-      &data,
+    image::save_buffer_with_format(
+      path,
+      &pixels,
       RESOLUTION[0],
       RESOLUTION[1],
       image::ColorType::Rgba8,
+      image::ImageFormat::Png,
     )?;
 
     Ok(())
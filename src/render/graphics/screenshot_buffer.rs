@@ -14,7 +14,7 @@ pub struct ScreenshotBuffer {
 
 impl ScreenshotBuffer {
   const PRIORITY: f32 = 0.2;
-  const BUFFER_SIZE: u64 = IMAGE_WITH_RESOLUTION_MINIMAL_SIZE;
+  pub const BUFFER_SIZE: u64 = IMAGE_WITH_RESOLUTION_MINIMAL_SIZE;
 
   // todo: change error name
   pub fn new(
@@ -11,14 +11,14 @@ use vkinitialization::{
   device::{Device, PhysicalDevice, QueueFamilies},
   Surface, SurfaceError,
 };
-use vkobjects::{errors::OutOfMemoryError, utility::OnErr, DeviceManuallyDestroyed};
+use vkobjects::{destroy, errors::OutOfMemoryError, utility::OnErr, DeviceManuallyDestroyed};
 use winit::dpi::PhysicalSize;
 
 use crate::{
   render::{
     create_objs::{create_fence, create_image_view, create_semaphore},
     errors::error_chain_fmt,
-    SWAPCHAIN_PREFERRED_IMAGE_FORMAT,
+    SWAPCHAIN_CLIPPED, SWAPCHAIN_PREFERRED_IMAGE_FORMAT,
   },
   PREFERRED_PRESENTATION_METHOD,
 };
@@ -508,7 +508,7 @@ impl Swapchain {
       pre_transform: capabilities.current_transform,
       composite_alpha: vk::CompositeAlphaFlagsKHR::OPAQUE,
       present_mode,
-      clipped: vk::TRUE,
+      clipped: SWAPCHAIN_CLIPPED.into(),
       old_swapchain,
       _marker: PhantomData,
     };
@@ -532,12 +532,18 @@ impl Swapchain {
       let mut image_views: Vec<vk::ImageView> = Vec::with_capacity(images.len());
       for &image in images.iter() {
         image_views.push(
-          match create_image_view(device, image, image_format.format) {
+          match create_image_view(
+            device,
+            image,
+            image_format.format,
+            #[cfg(feature = "vl")]
+            marker,
+            #[cfg(feature = "vl")]
+            c"Swapchain image view",
+          ) {
             Ok(view) => view,
             Err(err) => unsafe {
-              for view in image_views {
-                view.destroy_self(device);
-              }
+              image_views.as_slice().destroy_self(device);
               swapchain_loader.destroy_swapchain(swapchain, None);
               return Err(err.into());
             },
@@ -571,12 +577,7 @@ impl Swapchain {
         &name,
       )
       .on_err(|_err| unsafe {
-        for sem in image_finished_presenting_sem.iter() {
-          sem.destroy_self(device);
-        }
-        for view in image_views.iter() {
-          view.destroy_self(device);
-        }
+        destroy!(device => image_finished_presenting_sem.as_slice(), image_views.as_ref());
         swapchain_loader.destroy_swapchain(swapchain, None);
       })?;
 
@@ -601,15 +602,12 @@ impl Swapchain {
           &name,
         )
         .on_err(|_err| unsafe {
-          for fence in fences.iter() {
-            fence.destroy_self(device);
-          }
-          for sem in image_finished_presenting_sem.iter() {
-            sem.destroy_self(device);
-          }
-          for view in image_views.iter() {
-            view.destroy_self(device);
-          }
+          destroy!(
+            device =>
+            fences.as_slice(),
+            image_finished_presenting_sem.as_ref(),
+            image_views.as_ref()
+          );
           swapchain_loader.destroy_swapchain(swapchain, None);
         })?;
 
@@ -642,16 +640,10 @@ impl Swapchain {
 
   pub unsafe fn destroy_self(&self, loader: &ash::khr::swapchain::Device, device: &ash::Device) {
     if let Some(fences) = &self.image_finished_presenting_fence {
-      for fence in fences {
-        fence.destroy_self(device);
-      }
-    }
-    for sem in self.image_finished_presenting_sem.iter() {
-      sem.destroy_self(device);
-    }
-    for view in self.image_views.iter() {
-      view.destroy_self(device);
+      fences.destroy_self(device);
     }
+    self.image_finished_presenting_sem.destroy_self(device);
+    self.image_views.destroy_self(device);
     loader.destroy_swapchain(self.inner, None);
   }
 }
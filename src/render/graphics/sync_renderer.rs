@@ -1,5 +1,6 @@
 use std::{
   marker::PhantomData,
+  path::Path,
   ptr,
   sync::{atomic::Ordering, mpsc},
 };
@@ -12,7 +13,7 @@ use crate::{
   render::{
     compute::ComputeFrameResult,
     create_objs::{create_fence, create_semaphore},
-    graphics, FrameRenderError, InitializationError, GRAPHICS_FRAMES_IN_FLIGHT,
+    graphics, FrameRenderError, InitializationError, FRAME_WAIT_TIMEOUT, GRAPHICS_FRAMES_IN_FLIGHT,
   },
   DEBUG_PRINT_FRAME_INFO, SCREENSHOT_SAVE_FILE,
 };
@@ -99,7 +100,7 @@ impl SyncRenderer {
       self.renderer.init.device.wait_for_fences(
         &[self.frame_fences[cur_frame_i]],
         true,
-        u64::MAX,
+        FRAME_WAIT_TIMEOUT,
       )?;
     }
     if let Some(buffer_i) = self.in_use_particle_buffers_by_frame[cur_frame_i] {
@@ -108,6 +109,17 @@ impl SyncRenderer {
 
     // current frame resources are now safe to use as they are not being used by the GPU
 
+    if DEBUG_PRINT_FRAME_INFO {
+      if let Some((render, copy)) = unsafe { self.renderer.last_timings(cur_frame_i) } {
+        log::debug!(
+          "[Frame {}] Previous GPU timings: render pass {:?}, copy to swapchain {:?}",
+          cur_total_frame,
+          render,
+          copy
+        );
+      }
+    }
+
     let destroyed_old_swapchain = self
       .renderer
       .swapchains
@@ -128,7 +140,10 @@ impl SyncRenderer {
     if let Some((frame, format)) = self.saving_frame {
       if frame == cur_frame_i {
         self.saving_frame = None;
-        match self.renderer.save_screenshot_buffer_as_rgba8(format) {
+        match self
+          .renderer
+          .save_screenshot_buffer_as_png(format, Path::new(SCREENSHOT_SAVE_FILE))
+        {
           Ok(()) => {
             println!(
               "[Frame {}] Screenshot saved to {:?}",
@@ -1,3 +1,5 @@
+use std::sync::OnceLock;
+
 use ash::vk;
 use vkinitialization::{
   device::{
@@ -9,10 +11,13 @@ use vkinitialization::{
   Surface, SurfaceError,
 };
 
-use crate::render::{
-  format_conversions::KNOWN_FORMATS,
-  pipelines::{ComputePushConstants, GraphicsPushConstants},
-  TARGET_API_VERSION,
+use crate::{
+  render::{
+    initialization::driver_version::Vendor,
+    pipelines::{ComputePushConstants, GraphicsPushConstants},
+    TARGET_API_VERSION,
+  },
+  DEVICE_SELECTION_LOGGING, PREFER_DEDICATED_TRANSFER, RANK_DEVICES_BY_LOCAL_MEMORY,
 };
 
 fn supports_swapchain(device: vk::PhysicalDevice, surface: &Surface) -> Result<bool, SurfaceError> {
@@ -22,71 +27,315 @@ fn supports_swapchain(device: vk::PhysicalDevice, surface: &Surface) -> Result<b
   Ok(!formats.is_empty() && !present_modes.is_empty())
 }
 
-fn check_physical_device_capabilities(
+// vk 1.0 features the device must support
+// none are currently required, add any needed by new shaders here
+// (for example shader_storage_image_write_without_format)
+fn required_device_features() -> vk::PhysicalDeviceFeatures {
+  vk::PhysicalDeviceFeatures::default()
+}
+
+// checks that don't depend on a surface
+// returns the reason the device was rejected, or None if the device is capable
+fn check_physical_device_requirements(
   instance: &ash::Instance,
-  surface: &Surface,
   selection: &PhysicalDeviceSelection,
-) -> Result<bool, SurfaceError> {
+) -> Option<&'static str> {
   // Filter devices that are strictly not supported
   // Check for any features or limits required by the application
 
   if selection.properties.p10.api_version < TARGET_API_VERSION {
-    log::info!(
-      "Skipped physical device: Device API version is less than targeted by the application"
-    );
-    return Ok(false);
+    return Some("Device API version is less than targeted by the application");
   }
 
   // device supports any of the known formats
-  if !KNOWN_FORMATS
-    .iter()
-    .any(|&f| super::format_is_supported(instance, selection.physical_device, f))
-  {
-    log::error!("Skipped physical device: Device does not support any known format required by the application");
-    return Ok(false);
+  if super::choose_texture_format(instance, selection.physical_device).is_none() {
+    return Some("Device does not support any known format required by the application");
   }
 
-  if !selection.supported_extensions.swapchain
-    || !supports_swapchain(selection.physical_device, surface)?
-  {
-    log::warn!("Skipped physical device: Device does not support swapchain");
-    return Ok(false);
+  // the only required device extension
+  if !selection.supported_extensions.swapchain {
+    return Some("Device is missing required extension VK_KHR_swapchain");
+  }
+
+  if !super::check_feature_support(
+    instance,
+    selection.physical_device,
+    &required_device_features(),
+  ) {
+    return Some("Device does not support required features");
   }
 
   if selection.supported_features.f13.synchronization2 != vk::TRUE {
-    log::warn!("Skipped physical device: Device does not support synchronization features");
-    return Ok(false);
+    return Some("Device does not support synchronization features");
   }
 
   if (selection.properties.p10.limits.max_push_constants_size as usize)
     < size_of::<GraphicsPushConstants>().max(size_of::<ComputePushConstants>())
   {
-    log::error!("Skipped physical device: Device does not support required push constant size");
-    return Ok(false);
+    return Some("Device does not support required push constant size");
   }
 
-  Ok(true)
+  None
 }
 
-pub fn select_physical_device<'a>(
-  instance: &'a ash::Instance,
+// returns the reason the device was rejected, or None if the device is capable
+fn check_physical_device_capabilities(
+  instance: &ash::Instance,
   surface: &Surface,
-) -> Result<Option<PhysicalDeviceSelectionSuccess<'a>>, PhysicalDeviceSelectionError> {
+  selection: &PhysicalDeviceSelection,
+) -> Result<Option<&'static str>, SurfaceError> {
+  if let Some(reason) = check_physical_device_requirements(instance, selection) {
+    return Ok(Some(reason));
+  }
+
+  if !supports_swapchain(selection.physical_device, surface)? {
+    return Ok(Some(
+      "Device has no surface formats or present modes available for the window surface",
+    ));
+  }
+
+  Ok(None)
+}
+
+fn describe_device(selection: &PhysicalDeviceSelection) -> String {
+  let p10 = &selection.properties.p10;
+  format!(
+    "{:?}: type {:?}, API version {}.{}.{}, driver version {}",
+    p10.device_name_as_c_str().unwrap_or(c"unknown"),
+    p10.device_type,
+    vk::api_version_major(p10.api_version),
+    vk::api_version_minor(p10.api_version),
+    vk::api_version_patch(p10.api_version),
+    Vendor::from_id(p10.vendor_id).parse_driver_version(p10.driver_version)
+  )
+}
+
+fn log_device_properties(selection: &PhysicalDeviceSelection) {
+  log::info!("Physical device {}", describe_device(selection));
+}
+
+// prints every physical device and whether it meets the application requirements
+// surface support is not checked, as it requires a window
+pub fn list_physical_devices(instance: &ash::Instance) -> Result<(), PhysicalDeviceSelectionError> {
+  let selections = device_selector::enumerate_physical_devices_for_selection(instance)?;
+  println!("Found {} physical device(s):", selections.len());
+  for (i, selection) in selections.iter().enumerate() {
+    println!("  [{}] {}", i, describe_device(selection));
+    match check_physical_device_requirements(instance, selection) {
+      Some(reason) => println!("      not supported: {}", reason),
+      None => println!("      supported (surface support not checked)"),
+    }
+    super::log_available_device_extensions(instance, selection.physical_device);
+  }
+  Ok(())
+}
+
+// "device: reason" for every physical device that fails the application requirements
+// used to explain a failed selection regardless of SelectionLogging
+// starts with the reason the --device index was not used, if one was given
+pub fn describe_rejected_devices(instance: &ash::Instance, surface: &Surface) -> Vec<String> {
+  let selections = match device_selector::enumerate_physical_devices_for_selection(instance) {
+    Ok(selections) => selections,
+    Err(err) => return vec![format!("failed to enumerate physical devices: {}", err)],
+  };
+  if selections.is_empty() {
+    return vec!["no physical devices found".to_owned()];
+  }
+
+  let mut reasons: Vec<String> = selections
+    .iter()
+    .filter_map(|selection| {
+      let name = selection
+        .properties
+        .p10
+        .device_name_as_c_str()
+        .unwrap_or(c"unknown");
+      match check_physical_device_capabilities(instance, surface, selection) {
+        Ok(Some(reason)) => Some(format!("{:?}: {}", name, reason)),
+        Ok(None) => None,
+        Err(err) => Some(format!(
+          "{:?}: failed to query surface support: {}",
+          name, err
+        )),
+      }
+    })
+    .collect();
+
+  // the device chosen with --device is the only candidate, so explain it first
+  if let Some(&index) = DEVICE_OVERRIDE.get() {
+    let reason = if index < selections.len() {
+      format!("device index {} is not compatible", index)
+    } else {
+      format!(
+        "device index {} is out of range (found {})",
+        index,
+        selections.len()
+      )
+    };
+    reasons.insert(0, reason);
+  }
+  reasons
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SelectionLogging {
+  // log nothing
+  Silent,
+  // only log the selected device
+  Summary,
+  // log every candidate's properties and why it was rejected
+  Verbose,
+}
+
+// devices passing every application requirement, in enumeration order
+fn filter_compatible_devices(
+  instance: &ash::Instance,
+  surface: &Surface,
+  logging: SelectionLogging,
+) -> Result<Vec<(PhysicalDeviceSelection, QueueFamilies)>, PhysicalDeviceSelectionError> {
   let selections = device_selector::enumerate_physical_devices_for_selection(instance)?;
   let mut usable_devices = Vec::with_capacity(selections.len());
   for selection in selections {
-    let is_capable = check_physical_device_capabilities(instance, surface, &selection)?;
-    if is_capable {
+    if logging >= SelectionLogging::Verbose {
+      log_device_properties(&selection);
+    }
+    let rejection = check_physical_device_capabilities(instance, surface, &selection)?;
+    if let Some(reason) = rejection {
+      if logging >= SelectionLogging::Verbose {
+        log::info!("Skipped physical device: {}", reason);
+      }
+    } else {
       let queue_families =
         QueueFamilies::get_from_physical_device(instance, selection.physical_device, surface)?;
 
       usable_devices.push((selection, queue_families));
     }
   }
+  Ok(usable_devices)
+}
+
+// every compatible device with a display name, in enumeration order, for letting the user pick
+// one instead of relying on the ranking
+// the chosen entry can be returned as is from a selector passed to PhysicalDevice::select
+pub fn enumerate_compatible_devices<'a>(
+  instance: &'a ash::Instance,
+  surface: &Surface,
+) -> Result<Vec<(PhysicalDeviceSelectionSuccess<'a>, String)>, PhysicalDeviceSelectionError> {
+  Ok(
+    filter_compatible_devices(instance, surface, SelectionLogging::Silent)?
+      .into_iter()
+      .map(|(selection, queue_families)| {
+        let name = describe_device(&selection);
+        let success = PhysicalDeviceSelectionSuccess {
+          physical_device: selection.physical_device,
+          properties: selection.properties,
+          supported_extensions: selection.supported_extensions,
+          supported_features: selection.supported_features,
+          queue_families,
+        };
+        (success, name)
+      })
+      .collect(),
+  )
+}
+
+// index into the devices printed by --list-devices, set once from the command line
+static DEVICE_OVERRIDE: OnceLock<usize> = OnceLock::new();
+
+// makes select_physical_device pick the device at index instead of ranking all devices
+pub fn set_device_override(index: usize) {
+  if DEVICE_OVERRIDE.set(index).is_err() {
+    log::warn!("Device override was already set, ignoring index {}", index);
+  }
+}
+
+// selects the device at index in enumeration order (as printed by --list-devices)
+// returns None if there is no such device or if it is not compatible, which
+// describe_rejected_devices reports
+pub fn select_physical_device_by_index<'a>(
+  instance: &'a ash::Instance,
+  surface: &Surface,
+  index: usize,
+) -> Result<Option<PhysicalDeviceSelectionSuccess<'a>>, PhysicalDeviceSelectionError> {
+  let selections = device_selector::enumerate_physical_devices_for_selection(instance)?;
+  let Some(chosen) = selections
+    .get(index)
+    .map(|selection| selection.physical_device)
+  else {
+    return Ok(None);
+  };
+
+  let compatible = enumerate_compatible_devices(instance, surface)?;
+  let picked = compatible
+    .into_iter()
+    .find(|(success, _)| success.physical_device == chosen);
+  Ok(picked.map(|(success, name)| {
+    log::info!("Selected physical device {} by index {}", name, index);
+    success
+  }))
+}
+
+pub fn select_physical_device<'a>(
+  instance: &'a ash::Instance,
+  surface: &Surface,
+) -> Result<Option<PhysicalDeviceSelectionSuccess<'a>>, PhysicalDeviceSelectionError> {
+  if let Some(&index) = DEVICE_OVERRIDE.get() {
+    return select_physical_device_by_index(instance, surface, index);
+  }
+  let mut by_local_memory = rank_by_device_local_memory;
+  let ranking: Option<DeviceRanking> = if RANK_DEVICES_BY_LOCAL_MEMORY {
+    Some(&mut by_local_memory)
+  } else {
+    None
+  };
+  select_physical_device_with(instance, surface, DEVICE_SELECTION_LOGGING, ranking)
+}
+
+// Ranks each device by the vk 1.0 properties, chosen queue families and memory properties
+// Devices with a higher rank are preferred
+pub type DeviceRanking<'r> = &'r mut dyn FnMut(
+  &vk::PhysicalDeviceProperties,
+  &QueueFamilies,
+  &vk::PhysicalDeviceMemoryProperties,
+) -> i64;
+
+// total size of the device local heaps
+fn rank_by_device_local_memory(
+  _properties: &vk::PhysicalDeviceProperties,
+  _queue_families: &QueueFamilies,
+  memory_properties: &vk::PhysicalDeviceMemoryProperties,
+) -> i64 {
+  let local_bytes: u64 = memory_properties
+    .memory_heaps_as_slice()
+    .iter()
+    .filter(|heap| heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL))
+    .map(|heap| heap.size)
+    .sum();
+  i64::try_from(local_bytes).unwrap_or(i64::MAX)
+}
+
+// ranking replaces the default heuristic if provided
+pub fn select_physical_device_with<'a>(
+  instance: &'a ash::Instance,
+  surface: &Surface,
+  logging: SelectionLogging,
+  mut ranking: Option<DeviceRanking>,
+) -> Result<Option<PhysicalDeviceSelectionSuccess<'a>>, PhysicalDeviceSelectionError> {
+  let usable_devices = filter_compatible_devices(instance, surface, logging)?;
 
   let selected_device = usable_devices
     .into_iter()
     .min_by_key(|(selection, families)| {
+      let p10 = &selection.properties.p10;
+      let device_uuid = super::get_device_uuid(instance, selection.physical_device);
+      if let Some(rank) = ranking.as_mut() {
+        let memory_properties =
+          unsafe { instance.get_physical_device_memory_properties(selection.physical_device) };
+        // lowest key is selected
+        let score = rank(p10, families, &memory_properties).saturating_neg();
+        return (score, p10.vendor_id, p10.device_id, device_uuid);
+      }
+
       // Assign a score to each device and select the best one available
       // A full application may use multiple metrics like limits, queue families and even the
       // device id to rank each device that a user can have
@@ -95,7 +344,11 @@ pub fn select_physical_device<'a>(
       let device_score_importance = 0;
 
       // rank devices by number of specialized queue families
-      let transfer_score = if families.transfer.is_some() { 0 } else { 1 };
+      let transfer_score = if PREFER_DEDICATED_TRANSFER && families.transfer.is_none() {
+        1
+      } else {
+        0
+      };
       let queue_score = transfer_score;
 
       // rank devices by commonly most powerful device type
@@ -108,9 +361,27 @@ pub fn select_physical_device<'a>(
         _ => 5,
       };
 
-      (queue_score << queue_family_importance) + (device_score << device_score_importance)
+      let score: i64 =
+        (queue_score << queue_family_importance) + (device_score << device_score_importance);
+
+      // Ties are broken by (vendor_id, device_id, device_uuid), lowest first, so that the same set
+      // of devices always results in the same selection regardless of enumeration order, even
+      // with multiple identical GPUs
+      (score, p10.vendor_id, p10.device_id, device_uuid)
     });
 
+  if let Some((selection, _)) = selected_device
+    .as_ref()
+    .filter(|_| logging >= SelectionLogging::Summary)
+  {
+    let p10 = &selection.properties.p10;
+    log::info!(
+      "Selected physical device {:?} (driver version {})",
+      p10.device_name_as_c_str().unwrap_or(c"unknown"),
+      Vendor::from_id(p10.vendor_id).parse_driver_version(p10.driver_version)
+    );
+  }
+
   Ok(selected_device.map(
     |(selection, queue_families)| PhysicalDeviceSelectionSuccess {
       physical_device: selection.physical_device,
@@ -0,0 +1,86 @@
+use ash::vk;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vendor {
+  Nvidia,
+  Intel,
+  Amd,
+  Other(u32),
+}
+
+impl Vendor {
+  pub fn from_id(vendor_id: u32) -> Self {
+    match vendor_id {
+      0x10DE => Vendor::Nvidia,
+      0x8086 => Vendor::Intel,
+      0x1002 => Vendor::Amd,
+      other => Vendor::Other(other),
+    }
+  }
+
+  // driver_version is vendor specific and may not follow the standard vulkan version packing
+  pub fn parse_driver_version(self, v: u32) -> String {
+    match self {
+      // 10 bits major, 8 bits minor, 8 bits secondary branch, 6 bits tertiary branch
+      Vendor::Nvidia => format!(
+        "{}.{}.{}.{}",
+        (v >> 22) & 0x3ff,
+        (v >> 14) & 0x0ff,
+        (v >> 6) & 0x0ff,
+        v & 0x003f
+      ),
+      // the windows driver uses the high 18 bits for the major version and the low 14 for the minor
+      // mesa (linux) uses the standard format
+      #[cfg(target_os = "windows")]
+      Vendor::Intel => format!("{}.{}", v >> 14, v & 0x3fff),
+      _ => format!(
+        "{}.{}.{}",
+        vk::api_version_major(v),
+        vk::api_version_minor(v),
+        vk::api_version_patch(v)
+      ),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ash::vk;
+
+  use super::Vendor;
+
+  #[test]
+  fn from_id_maps_known_vendors() {
+    assert_eq!(Vendor::from_id(0x10DE), Vendor::Nvidia);
+    assert_eq!(Vendor::from_id(0x8086), Vendor::Intel);
+    assert_eq!(Vendor::from_id(0x1002), Vendor::Amd);
+    assert_eq!(Vendor::from_id(0x13B5), Vendor::Other(0x13B5));
+  }
+
+  #[test]
+  fn nvidia_driver_version() {
+    let v = (535 << 22) | (104 << 14) | (5 << 6) | 3;
+    assert_eq!(Vendor::Nvidia.parse_driver_version(v), "535.104.5.3");
+  }
+
+  #[test]
+  fn standard_driver_version() {
+    let v = vk::make_api_version(0, 23, 1, 4);
+    assert_eq!(Vendor::Amd.parse_driver_version(v), "23.1.4");
+    assert_eq!(Vendor::Other(0x13B5).parse_driver_version(v), "23.1.4");
+  }
+
+  #[cfg(not(target_os = "windows"))]
+  #[test]
+  fn intel_mesa_driver_version() {
+    let v = vk::make_api_version(0, 24, 0, 5);
+    assert_eq!(Vendor::Intel.parse_driver_version(v), "24.0.5");
+  }
+
+  #[cfg(target_os = "windows")]
+  #[test]
+  fn intel_windows_driver_version() {
+    let v = (101 << 14) | 4146;
+    assert_eq!(Vendor::Intel.parse_driver_version(v), "101.4146");
+  }
+}
@@ -1,9 +1,14 @@
 mod device_selector;
+mod driver_version;
 mod post_window_init;
 mod pre_window_init;
 
 use ash::vk;
-pub use device_selector::select_physical_device;
+pub use device_selector::{
+  describe_rejected_devices, list_physical_devices, select_physical_device, set_device_override,
+  SelectionLogging,
+};
+use vkinitialization::device::SingleQueues;
 
 use std::{marker::PhantomData, ptr};
 
@@ -11,7 +16,9 @@ pub use post_window_init::PostWindowInit;
 pub use pre_window_init::{PreWindowInit, PreWindowInitError};
 
 use crate::{
-  render::{graphics::TEXTURE_FORMAT_FEATURES, TARGET_API_VERSION},
+  render::{
+    format_conversions::KNOWN_FORMATS, graphics::TEXTURE_FORMAT_FEATURES, TARGET_API_VERSION,
+  },
   APPLICATION_NAME, APPLICATION_VERSION,
 };
 
@@ -32,11 +39,184 @@ pub fn format_is_supported(
   instance: &ash::Instance,
   physical_device: vk::PhysicalDevice,
   format: vk::Format,
+  tiling: vk::ImageTiling,
+  features: vk::FormatFeatureFlags,
 ) -> bool {
   let properties =
     unsafe { instance.get_physical_device_format_properties(physical_device, format) };
 
-  properties
-    .optimal_tiling_features
-    .contains(TEXTURE_FORMAT_FEATURES)
+  match tiling {
+    vk::ImageTiling::LINEAR => properties.linear_tiling_features.contains(features),
+    vk::ImageTiling::OPTIMAL => properties.optimal_tiling_features.contains(features),
+    _ => false,
+  }
+}
+
+// first format in candidates (in order of preference) that supports the features with the tiling
+pub fn choose_supported_format(
+  instance: &ash::Instance,
+  physical_device: vk::PhysicalDevice,
+  candidates: &[vk::Format],
+  tiling: vk::ImageTiling,
+  features: vk::FormatFeatureFlags,
+) -> Option<vk::Format> {
+  candidates
+    .iter()
+    .copied()
+    .find(|&format| format_is_supported(instance, physical_device, format, tiling, features))
+}
+
+// format used for the texture, following KNOWN_FORMATS order
+pub fn choose_texture_format(
+  instance: &ash::Instance,
+  physical_device: vk::PhysicalDevice,
+) -> Option<vk::Format> {
+  choose_supported_format(
+    instance,
+    physical_device,
+    &KNOWN_FORMATS,
+    vk::ImageTiling::OPTIMAL,
+    TEXTURE_FORMAT_FEATURES,
+  )
+}
+
+// one line summary of the chosen queue families, for example
+// "graphics=0 (dedicated), compute=0 (shared with graphics), transfer=2 (dedicated)"
+// queues without a dedicated family fall back to a more general one
+pub fn describe_queues(queues: &SingleQueues) -> String {
+  describe_queue_families(
+    queues.graphics.family_index,
+    queues.compute.family_index,
+    queues.transfer.family_index,
+  )
+}
+
+fn describe_queue_families(graphics_i: u32, compute_i: u32, transfer_i: u32) -> String {
+  let compute_sharing = if compute_i == graphics_i {
+    "shared with graphics"
+  } else {
+    "dedicated"
+  };
+  let transfer_sharing = if transfer_i == graphics_i {
+    "shared with graphics"
+  } else if transfer_i == compute_i {
+    "shared with compute"
+  } else {
+    "dedicated"
+  };
+
+  format!(
+    "graphics={} (dedicated), compute={} ({}), transfer={} ({})",
+    graphics_i, compute_i, compute_sharing, transfer_i, transfer_sharing
+  )
+}
+
+// subgroup (wave / warp) properties, relevant for choosing compute workgroup sizes
+pub fn get_subgroup_properties(
+  instance: &ash::Instance,
+  physical_device: vk::PhysicalDevice,
+) -> vk::PhysicalDeviceSubgroupProperties<'static> {
+  let mut subgroup = vk::PhysicalDeviceSubgroupProperties::default();
+  let mut properties2 = vk::PhysicalDeviceProperties2::default().push_next(&mut subgroup);
+  unsafe { instance.get_physical_device_properties2(physical_device, &mut properties2) };
+  subgroup
+}
+
+// identifies a single physical device, even between identical models
+pub fn get_device_uuid(
+  instance: &ash::Instance,
+  physical_device: vk::PhysicalDevice,
+) -> [u8; vk::UUID_SIZE] {
+  let mut id = vk::PhysicalDeviceIDProperties::default();
+  let mut properties2 = vk::PhysicalDeviceProperties2::default().push_next(&mut id);
+  unsafe { instance.get_physical_device_properties2(physical_device, &mut properties2) };
+  id.device_uuid
+}
+
+// whether every feature set in required is also supported by the device
+pub fn check_feature_support(
+  instance: &ash::Instance,
+  physical_device: vk::PhysicalDevice,
+  required: &vk::PhysicalDeviceFeatures,
+) -> bool {
+  let available = unsafe { instance.get_physical_device_features(physical_device) };
+  feature_flags(required)
+    .iter()
+    .zip(feature_flags(&available))
+    .all(|(&required, &available)| required == vk::FALSE || available == vk::TRUE)
+}
+
+// vk::PhysicalDeviceFeatures is a repr(C) struct made only of vk::Bool32 fields, so it can be
+// compared field by field as a slice without listing every feature
+fn feature_flags(features: &vk::PhysicalDeviceFeatures) -> &[vk::Bool32] {
+  unsafe {
+    std::slice::from_raw_parts(
+      (features as *const vk::PhysicalDeviceFeatures).cast::<vk::Bool32>(),
+      size_of::<vk::PhysicalDeviceFeatures>() / size_of::<vk::Bool32>(),
+    )
+  }
+}
+
+pub fn log_available_instance_extensions(entry: &ash::Entry) {
+  match unsafe { entry.enumerate_instance_extension_properties(None) } {
+    Ok(extensions) => {
+      log::debug!("Available instance extensions ({}):", extensions.len());
+      for ext in extensions.iter() {
+        log::debug!(
+          "  {:?} (spec version {})",
+          ext.extension_name_as_c_str().unwrap_or(c"unknown"),
+          ext.spec_version
+        );
+      }
+    }
+    Err(err) => log::warn!("Failed to enumerate instance extensions: {}", err),
+  }
+}
+
+pub fn log_available_device_extensions(
+  instance: &ash::Instance,
+  physical_device: vk::PhysicalDevice,
+) {
+  match unsafe { instance.enumerate_device_extension_properties(physical_device) } {
+    Ok(extensions) => {
+      log::debug!("Available device extensions ({}):", extensions.len());
+      for ext in extensions.iter() {
+        log::debug!(
+          "  {:?} (spec version {})",
+          ext.extension_name_as_c_str().unwrap_or(c"unknown"),
+          ext.spec_version
+        );
+      }
+    }
+    Err(err) => log::warn!("Failed to enumerate device extensions: {}", err),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::describe_queue_families;
+
+  #[test]
+  fn dedicated_queue_families() {
+    assert_eq!(
+      describe_queue_families(0, 1, 2),
+      "graphics=0 (dedicated), compute=1 (dedicated), transfer=2 (dedicated)"
+    );
+  }
+
+  #[test]
+  fn queue_families_shared_with_graphics() {
+    assert_eq!(
+      describe_queue_families(0, 0, 0),
+      "graphics=0 (dedicated), compute=0 (shared with graphics), transfer=0 (shared with graphics)"
+    );
+  }
+
+  #[test]
+  fn transfer_shared_with_compute() {
+    assert_eq!(
+      describe_queue_families(0, 1, 1),
+      "graphics=0 (dedicated), compute=1 (dedicated), transfer=1 (shared with compute)"
+    );
+  }
 }
@@ -6,6 +6,8 @@ use vkinitialization::{
 use vkobjects::{destroy, utility::OnErr, ManuallyDestroyed};
 use winit::{dpi::PhysicalSize, event_loop::ActiveEventLoop, window::Window};
 
+#[cfg(feature = "vl")]
+use crate::render::debug_labels::CommandLabels;
 use crate::{
   render::{compute::ferris::Ferris, initialization, InitializationError},
   INITIAL_WINDOW_HEIGHT, INITIAL_WINDOW_WIDTH, WINDOW_TITLE,
@@ -18,6 +20,8 @@ pub struct PostWindowInit {
   pub debug_utils: vkinitialization::DebugUtils,
   #[cfg(feature = "vl")]
   pub debug_utils_marker: vkinitialization::DebugUtilsMarker,
+  #[cfg(feature = "vl")]
+  pub command_labels: CommandLabels,
   pub physical_device: PhysicalDevice,
   pub device: Device,
   pub queues: SingleQueues,
@@ -72,8 +76,9 @@ impl PostWindowInit {
     {
       Some(tu) => tu,
       None => {
+        let rejections = initialization::describe_rejected_devices(&instance, &surface);
         destroy_instance();
-        return Err(InitializationError::NoCompatibleDevices);
+        return Err(InitializationError::NoCompatibleDevices(rejections));
       }
     };
 
@@ -103,12 +108,26 @@ impl PostWindowInit {
 
     let physical_device = physical_device_creation.physical_device;
 
+    log::info!(
+      "Queue families: {}",
+      initialization::describe_queues(&queues)
+    );
+
+    let subgroup_properties = initialization::get_subgroup_properties(&instance, *physical_device);
+    log::info!(
+      "Subgroup size {}, supported operations {:?}",
+      subgroup_properties.subgroup_size,
+      subgroup_properties.supported_operations
+    );
+
     #[cfg(feature = "vl")]
     let debug_utils_marker = vkinitialization::DebugUtilsMarker::new(&instance, &device);
     #[cfg(feature = "vl")]
     unsafe {
       debug_utils_marker.set_queue_labels(queues);
     }
+    #[cfg(feature = "vl")]
+    let command_labels = CommandLabels::new(&instance, &device);
 
     Ok(Self {
       window,
@@ -119,6 +138,8 @@ impl PostWindowInit {
       debug_utils,
       #[cfg(feature = "vl")]
       debug_utils_marker,
+      #[cfg(feature = "vl")]
+      command_labels,
       physical_device,
       device,
       queues,
@@ -1,8 +1,11 @@
+use ash::vk;
 use raw_window_handle::{HandleError, HasDisplayHandle};
 use vkinitialization::{InstanceCreationError, InstanceOptionalExtensions};
 use vkobjects::ManuallyDestroyed;
 use winit::event_loop::EventLoop;
 
+use crate::render::TARGET_API_VERSION;
+
 use std::mem;
 
 use std::{
@@ -25,6 +28,16 @@ pub enum PreWindowInitError {
 
   #[error("Failed to get display handle")]
   DisplayHandle(#[source] HandleError),
+
+  #[error(
+    "The Vulkan loader supports instance version {}.{}.{}, but the application requires {}.{}",
+    vk::api_version_major(*.available),
+    vk::api_version_minor(*.available),
+    vk::api_version_patch(*.available),
+    vk::api_version_major(*.required),
+    vk::api_version_minor(*.required)
+  )]
+  UnsupportedInstanceVersion { available: u32, required: u32 },
 }
 
 impl From<InstanceCreationError> for PreWindowInitError {
@@ -33,9 +46,40 @@ impl From<InstanceCreationError> for PreWindowInitError {
   }
 }
 
+// an older loader may still create the instance, after which every device gets rejected for a
+// low api version; checking first gives a clearer error
+fn check_instance_version(entry: &ash::Entry) -> Result<(), PreWindowInitError> {
+  let available = match unsafe { entry.try_enumerate_instance_version() } {
+    // None means the loader only supports 1.0
+    Ok(version) => version.unwrap_or(vk::API_VERSION_1_0),
+    Err(err) => {
+      log::warn!("Failed to query the instance version: {}", err);
+      return Ok(());
+    }
+  };
+
+  if available < TARGET_API_VERSION {
+    return Err(PreWindowInitError::UnsupportedInstanceVersion {
+      available,
+      required: TARGET_API_VERSION,
+    });
+  }
+  Ok(())
+}
+
 impl PreWindowInit {
   pub fn new(event_loop: &EventLoop<()>) -> Result<Self, PreWindowInitError> {
     let entry: ash::Entry = unsafe { vkinitialization::get_entry() };
+    Self::new_with_entry(entry, event_loop)
+  }
+
+  // allows sharing an already loaded entry instead of loading the Vulkan library again
+  pub fn new_with_entry(
+    entry: ash::Entry,
+    event_loop: &EventLoop<()>,
+  ) -> Result<Self, PreWindowInitError> {
+    super::log_available_instance_extensions(&entry);
+    check_instance_version(&entry)?;
 
     let display_handle = event_loop
       .display_handle()
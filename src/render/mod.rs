@@ -1,6 +1,8 @@
 mod command_pools;
 pub mod compute;
 mod create_objs;
+#[cfg(feature = "vl")]
+mod debug_labels;
 mod descriptor_sets;
 mod errors;
 mod format_conversions;
@@ -15,7 +17,10 @@ use vkobjects::const_flag_bitor;
 
 pub use errors::{FrameRenderError, InitializationError};
 pub use graphics::AcquireNextImageError;
-pub use initialization::{PostWindowInit, PreWindowInit, PreWindowInitError};
+pub use initialization::{
+  list_physical_devices, set_device_override, PostWindowInit, PreWindowInit, PreWindowInitError,
+  SelectionLogging,
+};
 
 use crate::RESOLUTION;
 
@@ -25,7 +30,21 @@ const COMPUTE_FRAMES_IN_FLIGHT: usize = 2;
 
 const TARGET_API_VERSION: u32 = vk::API_VERSION_1_3;
 
+// how long (in nanoseconds) to wait for initialization work submitted to the GPU before giving up
+const INITIALIZATION_WAIT_TIMEOUT: u64 = 10_000_000_000;
+
+// how long (in nanoseconds) to wait for a previous frame's fence before assuming the device hung
+const FRAME_WAIT_TIMEOUT: u64 = 5_000_000_000;
+
 const SWAPCHAIN_IMAGE_USAGES: vk::ImageUsageFlags = const_flag_bitor!(vk::ImageUsageFlags => vk::ImageUsageFlags::COLOR_ATTACHMENT, vk::ImageUsageFlags::TRANSFER_DST);
+// allows the presentation engine to discard pixels obscured by other windows, leaving their
+// contents undefined
+// screenshots are copied from the render targets, not the swapchain, so they are not affected
+const SWAPCHAIN_CLIPPED: bool = true;
+// obscured regions would be undefined if the swapchain images were read back
+const _: () = assert!(
+  !(SWAPCHAIN_CLIPPED && SWAPCHAIN_IMAGE_USAGES.contains(vk::ImageUsageFlags::TRANSFER_SRC))
+);
 
 pub const RENDER_EXTENT: vk::Extent2D = vk::Extent2D {
   width: RESOLUTION[0],
@@ -19,6 +19,10 @@ const MAGIC: u32 = 0x74c1887f;
 const TEMP_PATH: &str = "./pipeline_cache.temp";
 const PATH: &str = "pipeline_cache";
 
+// fail instead of starting with an empty cache if the cache file was created by a different
+// device or driver
+const STRICT_CACHE_COMPATIBILITY: bool = false;
+
 fn hash_data(data: &Vec<u8>) -> u64 {
   let mut hasher = DefaultHasher::new();
   data.hash(&mut hasher);
@@ -46,6 +50,8 @@ pub enum PipelineCacheError {
   IOError(#[from] io::Error),
   #[error(transparent)]
   OutOfMemoryError(#[from] OutOfMemoryError),
+  #[error("Pipeline cache data was created by a different device or driver")]
+  IncompatibleCache,
 }
 
 impl PipelineCacheHeader {
@@ -63,13 +69,16 @@ impl PipelineCacheHeader {
     }
   }
 
+  // header was written by this application on this platform
+  fn is_valid(&self) -> bool {
+    self.magic == MAGIC && self.driver_abi == size_of::<*const c_void>() as u32
+  }
+
   fn is_compatible(&self, physical_device: &PhysicalDevice) -> bool {
     let props = &physical_device.properties;
-    self.magic == MAGIC
-      && self.vendor_id == props.p10.vendor_id
+    self.vendor_id == props.p10.vendor_id
       && self.device_id == props.p10.device_id
       && self.driver_version == props.p10.driver_version
-      && self.driver_abi == size_of::<*const c_void>() as u32
       && self.cache_uuid == props.p10.pipeline_cache_uuid
   }
 
@@ -82,6 +91,26 @@ impl PipelineCacheHeader {
   }
 }
 
+// checks the header the implementation writes at the start of the pipeline cache data
+// the file header already identifies the device and driver, so a mismatch here means the data
+// was corrupted rather than created elsewhere
+// https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPipelineCacheHeaderVersionOne.html
+fn vulkan_header_is_compatible(physical_device: &PhysicalDevice, data: &[u8]) -> bool {
+  // headerSize, headerVersion, vendorID, deviceID, pipelineCacheUUID
+  const HEADER_VERSION_ONE_SIZE: usize = 4 * 4 + vk::UUID_SIZE;
+  if data.len() < HEADER_VERSION_ONE_SIZE {
+    return false;
+  }
+  let read_u32 = |offset: usize| u32::from_ne_bytes(data[offset..(offset + 4)].try_into().unwrap());
+
+  let props = &physical_device.properties;
+  read_u32(0) as usize >= HEADER_VERSION_ONE_SIZE
+    && read_u32(4) == vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+    && read_u32(8) == props.p10.vendor_id
+    && read_u32(12) == props.p10.device_id
+    && data[16..HEADER_VERSION_ONE_SIZE] == props.p10.pipeline_cache_uuid
+}
+
 // tries to save the pipeline cache data to a file
 pub fn save_pipeline_cache(
   device: &ash::Device,
@@ -114,6 +143,10 @@ pub fn create_pipeline_cache(
 ) -> Result<(vk::PipelineCache, bool), PipelineCacheError> {
   // tries to create a pipeline cache from an existing file
   let cache_result = match try_read_pipeline_cache_data_from_file(physical_device) {
+    Ok(data) if !vulkan_header_is_compatible(physical_device, &data) => {
+      log::error!("Pipeline cache data has an invalid Vulkan header, discarding it");
+      Err(())
+    }
     Ok(data) => {
       let create_info = vk::PipelineCacheCreateInfo::default().initial_data(&data);
       let result = unsafe { device.create_pipeline_cache(&create_info, None) };
@@ -125,7 +158,14 @@ pub fn create_pipeline_cache(
         );
       })
     }
-    Err(err) => {
+    Err(CacheReadError::Incompatible) => {
+      if STRICT_CACHE_COMPATIBILITY {
+        return Err(PipelineCacheError::IncompatibleCache);
+      }
+      log::warn!("Pipeline cache file was created by a different device or driver, discarding it");
+      Err(())
+    }
+    Err(CacheReadError::IOError(err)) => {
       // it's okay if file doesn't exist
       if err.kind() != io::ErrorKind::NotFound {
         log::error!(
@@ -152,27 +192,50 @@ pub fn create_pipeline_cache(
   }
 }
 
-fn try_read_pipeline_cache_data_from_file(physical_device: &PhysicalDevice) -> io::Result<Vec<u8>> {
+enum CacheReadError {
+  IOError(io::Error),
+  // valid file created by a different device or driver
+  Incompatible,
+}
+
+impl From<io::Error> for CacheReadError {
+  fn from(value: io::Error) -> Self {
+    CacheReadError::IOError(value)
+  }
+}
+
+fn try_read_pipeline_cache_data_from_file(
+  physical_device: &PhysicalDevice,
+) -> Result<Vec<u8>, CacheReadError> {
   let mut file = File::open(PATH)?;
 
   let mut header_bytes = [0u8; size_of::<PipelineCacheHeader>()];
   file.read_exact(&mut header_bytes)?;
 
   let header = unsafe { PipelineCacheHeader::from_bytes(header_bytes) };
+  if !header.is_valid() {
+    return Err(
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        "Header file is incompatible or corrupted",
+      )
+      .into(),
+    );
+  }
   if !header.is_compatible(physical_device) {
-    return Err(io::Error::new(
-      io::ErrorKind::InvalidData,
-      "Header file is incompatible or corrupted",
-    ));
+    return Err(CacheReadError::Incompatible);
   }
 
   let mut data = Vec::new();
   file.read_to_end(&mut data)?;
   if data.len() != header.data_size as usize || hash_data(&data) != header.data_hash {
-    return Err(io::Error::new(
-      io::ErrorKind::InvalidData,
-      "Pipeline cache data is corrupted",
-    ));
+    return Err(
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        "Pipeline cache data is corrupted",
+      )
+      .into(),
+    );
   }
 
   Ok(data)
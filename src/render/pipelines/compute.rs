@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, mem::size_of, ptr};
+use std::{ffi::c_void, marker::PhantomData, mem::size_of, ptr};
 
 use ash::vk;
 use vkobjects::{errors::OutOfMemoryError, DeviceManuallyDestroyed};
@@ -36,9 +36,11 @@ impl ComputePipeline {
       shaders::compute::Shader::load(device).map_err(PipelineCreationError::ShaderFailed)?;
     let shader_stages = shader.get_pipeline_shader_creation_info();
 
+    let mut feedback = vk::PipelineCreationFeedback::default();
+    let feedback_info = super::creation_feedback_info(&mut feedback);
     let create_info = vk::ComputePipelineCreateInfo {
       s_type: vk::StructureType::COMPUTE_PIPELINE_CREATE_INFO,
-      p_next: ptr::null(),
+      p_next: ptr::addr_of!(feedback_info) as *const c_void,
       stage: shader_stages,
       flags: vk::PipelineCreateFlags::empty(),
       layout,
@@ -55,6 +57,7 @@ impl ComputePipeline {
         vk::Result::ERROR_INVALID_SHADER_NV => PipelineCreationError::CompilationFailed,
         _ => panic!(),
       })?[0];
+    super::log_creation_feedback("compute", &feedback);
 
     unsafe {
       shader.destroy_self(device);
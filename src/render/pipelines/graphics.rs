@@ -1,4 +1,5 @@
 use std::{
+  ffi::c_void,
   marker::PhantomData,
   mem::{self, size_of},
   ops::BitOr,
@@ -201,9 +202,11 @@ impl GraphicsPipeline {
     if !base.is_null() {
       flags = flags.bitor(vk::PipelineCreateFlags::DERIVATIVE)
     }
+    let mut feedback = vk::PipelineCreationFeedback::default();
+    let feedback_info = super::creation_feedback_info(&mut feedback);
     let create_info = vk::GraphicsPipelineCreateInfo {
       s_type: vk::StructureType::GRAPHICS_PIPELINE_CREATE_INFO,
-      p_next: ptr::null(),
+      p_next: ptr::addr_of!(feedback_info) as *const c_void,
       flags,
       stage_count: shader_stages.len() as u32,
       p_stages: shader_stages.as_ptr(),
@@ -223,7 +226,7 @@ impl GraphicsPipeline {
       base_pipeline_index: -1, // -1 for null
       _marker: PhantomData,
     };
-    Ok(unsafe {
+    let pipeline = unsafe {
       device
         .create_graphics_pipelines(cache, &[create_info], None)
         .map_err(|incomplete| incomplete.1)
@@ -234,7 +237,10 @@ impl GraphicsPipeline {
           vk::Result::ERROR_INVALID_SHADER_NV => PipelineCreationError::CompilationFailed,
           _ => panic!(),
         })?[0]
-    })
+    };
+    super::log_creation_feedback("graphics", &feedback);
+
+    Ok(pipeline)
   }
 }
 
@@ -5,6 +5,13 @@ mod graphics;
 pub use cache::{create_pipeline_cache, save_pipeline_cache, PipelineCacheError};
 pub use compute::{ComputePipeline, ComputePushConstants};
 pub use graphics::{GraphicsPipeline, GraphicsPushConstants};
+use std::{
+  marker::PhantomData,
+  ptr,
+  time::{Duration, Instant},
+};
+
+use ash::vk;
 use vkobjects::errors::OutOfMemoryError;
 
 use crate::render::shaders::ShaderError;
@@ -24,3 +31,60 @@ impl From<OutOfMemoryError> for PipelineCreationError {
     PipelineCreationError::OutOfMemory(value)
   }
 }
+
+// VK_EXT_pipeline_creation_feedback is core in Vulkan 1.3
+// feedback must be kept alive and not moved until after the pipeline is created
+fn creation_feedback_info(
+  feedback: &mut vk::PipelineCreationFeedback,
+) -> vk::PipelineCreationFeedbackCreateInfo<'_> {
+  vk::PipelineCreationFeedbackCreateInfo {
+    s_type: vk::StructureType::PIPELINE_CREATION_FEEDBACK_CREATE_INFO,
+    p_next: ptr::null(),
+    p_pipeline_creation_feedback: feedback,
+    pipeline_stage_creation_feedback_count: 0,
+    p_pipeline_stage_creation_feedbacks: ptr::null_mut(),
+    _marker: PhantomData,
+  }
+}
+
+fn log_creation_feedback(pipeline_name: &str, feedback: &vk::PipelineCreationFeedback) {
+  // implementations are allowed to not write any feedback, in which case VALID is not set
+  if !feedback
+    .flags
+    .contains(vk::PipelineCreationFeedbackFlags::VALID)
+  {
+    log::debug!(
+      "Driver did not provide creation feedback for the {} pipeline",
+      pipeline_name
+    );
+    return;
+  }
+
+  log::info!(
+    "Created {} pipeline in {:?} (pipeline cache hit: {})",
+    pipeline_name,
+    Duration::from_nanos(feedback.duration),
+    feedback
+      .flags
+      .contains(vk::PipelineCreationFeedbackFlags::APPLICATION_PIPELINE_CACHE_HIT)
+  );
+}
+
+// runs and times the creation of a pipeline, logging whether the pipeline cache started populated
+pub fn time_pipeline_creation<T, E>(
+  pipeline_name: &str,
+  cache_warm: bool,
+  create: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+  let start = Instant::now();
+  let result = create();
+  if result.is_ok() {
+    log::info!(
+      "{} pipeline creation took {:?} with a {} pipeline cache",
+      pipeline_name,
+      start.elapsed(),
+      if cache_warm { "warm" } else { "cold (empty)" }
+    );
+  }
+  result
+}
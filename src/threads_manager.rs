@@ -44,6 +44,8 @@ impl ThreadsManager {
       post_window_init.physical_device.clone(),
       post_window_init.queues.clone(),
       post_window_init.debug_utils_marker.clone(),
+      #[cfg(feature = "vl")]
+      post_window_init.command_labels.clone(),
     )?;
 
     let compute_thread_data = ComputeThreadData {
@@ -109,6 +111,14 @@ impl ThreadsManager {
   pub fn screenshot(&mut self) {
     self.graphics_render.screenshot();
   }
+
+  // normalized rgba, applied from the next recorded frame
+  pub fn set_background_color(&mut self, color: [f32; 4]) {
+    self
+      .graphics_render
+      .renderer
+      .set_normalized_background_color(color);
+  }
 }
 
 impl Drop for ThreadsManager {